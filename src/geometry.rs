@@ -0,0 +1,416 @@
+//! Points and directions on a two-dimensional plane.
+//!
+//! Like [`Grid`](crate::grid::Grid), coordinates grow to the right (`x`) and downwards (`y`), so [`Direction::Up`]
+//! decreases `y`.
+//!
+//! ```ignore
+//! # use advent_of_code::geometry::{Direction, Point};
+//! let direction: Direction = "R".parse().unwrap();
+//! let point = Point::new(0_usize, 0).checked_move(direction, (10, 10));
+//! assert_eq!(point, Some(Point::new(1, 0)));
+//! ```
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A point, or a vector between two points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// The Manhattan (taxicab) distance to `other`. Works for unsigned coordinates as well.
+    pub fn manhattan(self, other: Self) -> T {
+        let abs_diff = |a: T, b: T| if a > b { a - b } else { b - a };
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+}
+
+impl Point<usize> {
+    /// Adds a signed `offset`, returning `None` if the result would be negative.
+    pub fn checked_add_signed(self, offset: Point<isize>) -> Option<Self> {
+        Some(Point::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+
+    /// Moves by `step`, returning `None` if the result lies outside of `(width, height)`.
+    pub fn checked_move(
+        self,
+        step: impl Into<Point<isize>>,
+        (width, height): (usize, usize),
+    ) -> Option<Self> {
+        self.checked_add_signed(step.into())
+            .filter(|point| point.x < width && point.y < height)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+/// Scales the point by a factor.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Add<Direction> for Point<isize> {
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.offset()
+    }
+}
+
+impl Add<Direction8> for Point<isize> {
+    type Output = Self;
+
+    fn add(self, rhs: Direction8) -> Self::Output {
+        self + rhs.offset()
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirectionError(String);
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not a direction.", self.0)
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting with [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The change of position when moving one step in this direction.
+    pub const fn offset(self) -> Point<isize> {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    /// The direction after a 90 degree turn counterclockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    /// The direction after a 90 degree turn clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The opposite direction.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// Parses `U`/`D`/`L`/`R`, `N`/`E`/`S`/`W` and `^`/`v`/`<`/`>`.
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value.to_ascii_uppercase() {
+            'U' | 'N' | '^' => Ok(Direction::Up),
+            'R' | 'E' | '>' => Ok(Direction::Right),
+            'D' | 'S' | 'V' => Ok(Direction::Down),
+            'L' | 'W' | '<' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(value.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(ParseDirectionError(s.into())),
+        }
+    }
+}
+
+impl From<Direction> for Point<isize> {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the eight compass directions, including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting with [`Direction8::N`].
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    /// The change of position when moving one step in this direction.
+    pub const fn offset(self) -> Point<isize> {
+        match self {
+            Direction8::N => Point::new(0, -1),
+            Direction8::NE => Point::new(1, -1),
+            Direction8::E => Point::new(1, 0),
+            Direction8::SE => Point::new(1, 1),
+            Direction8::S => Point::new(0, 1),
+            Direction8::SW => Point::new(-1, 1),
+            Direction8::W => Point::new(-1, 0),
+            Direction8::NW => Point::new(-1, -1),
+        }
+    }
+
+    /// The direction after a 45 degree turn counterclockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    /// The direction after a 45 degree turn clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The opposite direction.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+/// Parses compass names like `NE`, as well as everything [`Direction`] parses.
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "NE" => Ok(Direction8::NE),
+            "SE" => Ok(Direction8::SE),
+            "SW" => Ok(Direction8::SW),
+            "NW" => Ok(Direction8::NW),
+            _ => s
+                .parse::<Direction>()
+                .map(Direction8::from)
+                .map_err(|_| ParseDirectionError(s.into())),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::N,
+            Direction::Right => Direction8::E,
+            Direction::Down => Direction8::S,
+            Direction::Left => Direction8::W,
+        }
+    }
+}
+
+impl From<Direction8> for Point<isize> {
+    fn from(direction: Direction8) -> Self {
+        direction.offset()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Direction8, ParseDirectionError, Point};
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(a - b, Point::new(-3, 4));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(-a, Point::new(-1, -2));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(1, 1);
+        assert_eq!(c, Point::new(4, -1));
+        assert_eq!(Point::new(2, 3) + Direction::Up, Point::new(2, 2));
+        assert_eq!(<(i32, i32)>::from(a), (1, 2));
+    }
+
+    #[test]
+    fn measures_manhattan_distance() {
+        assert_eq!(Point::new(1, 2).manhattan(Point::new(4, -2)), 7);
+        assert_eq!(Point::new(5_usize, 1).manhattan(Point::new(2, 3)), 5);
+    }
+
+    #[test]
+    fn moves_within_bounds() {
+        let origin = Point::new(0_usize, 0);
+        assert_eq!(origin.checked_move(Direction::Left, (3, 3)), None);
+        assert_eq!(
+            origin.checked_move(Direction8::SE, (3, 3)),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(
+            Point::new(2_usize, 0).checked_move(Direction::Right, (3, 3)),
+            None
+        );
+        assert_eq!(
+            origin.checked_add_signed(Point::new(5, 7)),
+            Some(Point::new(5, 7))
+        );
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+        assert_eq!(Direction8::N.turn_left(), Direction8::NW);
+        assert_eq!(Direction8::NW.turn_right(), Direction8::N);
+        assert_eq!(Direction8::NE.reverse(), Direction8::SW);
+
+        for direction in Direction8::ALL {
+            assert_eq!(
+                direction.offset() + direction.reverse().offset(),
+                Point::new(0, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn parses_directions() {
+        for (s, expected) in [
+            ("U", Direction::Up),
+            ("n", Direction::Up),
+            ("^", Direction::Up),
+            ("R", Direction::Right),
+            ("E", Direction::Right),
+            (">", Direction::Right),
+            ("D", Direction::Down),
+            ("S", Direction::Down),
+            ("v", Direction::Down),
+            ("L", Direction::Left),
+            ("W", Direction::Left),
+            ("<", Direction::Left),
+        ] {
+            assert_eq!(s.parse(), Ok(expected), "{s}");
+        }
+
+        assert_eq!(
+            "UD".parse::<Direction>(),
+            Err(ParseDirectionError("UD".into()))
+        );
+        assert_eq!("sw".parse(), Ok(Direction8::SW));
+        assert_eq!("^".parse(), Ok(Direction8::N));
+        assert!("X".parse::<Direction8>().is_err());
+    }
+}
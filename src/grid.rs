@@ -0,0 +1,488 @@
+//! A two-dimensional grid of cells, as found in many puzzle inputs.
+//!
+//! Positions are `(x, y)` tuples, where `x` is the column and `y` is the row, counted from the top left corner.
+//!
+//! ```ignore
+//! # use advent_of_code::grid::Grid;
+//! let grid: Grid<char> = "#.\n..".parse().unwrap();
+//! assert_eq!(grid.get((0, 0)), Some(&'#'));
+//! assert_eq!(grid.neighbours4((0, 0)).count(), 2);
+//! ```
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geometry::Point;
+
+/// The offsets of the four orthogonal neighbours: up, right, down, left.
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The offsets of all eight neighbours, clockwise starting at the top.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A row does not have the same number of cells as the first row.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The number of cells is not a multiple of the width.
+    InvalidLength { width: usize, len: usize },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row."
+            ),
+            GridError::InvalidLength { width, len } => {
+                write!(f, "{len} cells do not fill rows of width {width}.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` by `height` cells, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from cells in row order.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len().checked_rem(width).unwrap_or(cells.len()) != 0 {
+            return Err(GridError::InvalidLength {
+                width,
+                len: cells.len(),
+            });
+        }
+
+        Ok(Grid {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        })
+    }
+
+    /// Parses the lines of `input` into a grid, converting each character with `cell`.
+    /// Empty lines at the end of the input are ignored, all other lines need to have the same length.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let lines: Vec<&str> = input.trim_end_matches(['\n', '\r']).lines().collect();
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (row, line) in lines.iter().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+
+            if cells.len() - len != width {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected: width,
+                    found: cells.len() - len,
+                });
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` lies inside the grid.
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn index_of(&self, pos: (usize, usize)) -> Option<usize> {
+        self.contains(pos).then(|| pos.1 * self.width + pos.0)
+    }
+
+    /// Returns the cell at `pos`, or `None` if it is out of bounds.
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    /// Returns the cell at `pos` mutably, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves from `pos` by `(dx, dy)`, returning `None` if the target is out of bounds.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The up to four orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// The up to eight neighbours of `pos`, including diagonals, that lie inside the grid.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// All positions in row order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// All cells mutably with their positions, in row order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    /// The cells of row `y`, or `None` if it is out of bounds.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// All rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// The cells of column `x` from top to bottom, or `None` if it is out of bounds.
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// All columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// The position of the first cell in row order that matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The positions of all cells that match `predicate`, in row order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Converts every cell with `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Builds a grid of `width` by `height` cells, where each cell is taken from `self` at `source(x, y)`.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(self.height, self.width, move |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height, self.width, move |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirrors the grid left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.width, self.height, move |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirrors the grid top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(self.width, self.height, move |x, y| (x, height - 1 - y))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    /// Panics if `pos` is out of bounds.
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos:?} is out of bounds of a {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    /// # Panics
+    /// Panics if `pos` is out of bounds.
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| {
+            panic!("position {pos:?} is out of bounds of a {width}x{height} grid")
+        })
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &Self::Output {
+        &self[(point.x, point.y)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut Self::Output {
+        &mut self[(point.x, point.y)]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, |c| c)
+    }
+}
+
+/// Prints one line per row, with the cells of a row written next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridError};
+    use crate::geometry::Point;
+
+    fn get_mock_grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_input() {
+        let grid = get_mock_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(
+            digits.rows().map(<[u32]>::to_vec).collect::<Vec<_>>(),
+            [[1, 2], [3, 4]]
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert!(Grid::from_vec(2, vec![1, 2, 3]).is_err());
+        assert_eq!(Grid::from_vec(2, vec![1, 2, 3, 4]).unwrap().height(), 2);
+        assert!(Grid::from_vec(0, vec![1]).is_err());
+        assert_eq!(Grid::<u8>::from_vec(0, vec![]).unwrap().height(), 0);
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid = get_mock_grid();
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (2, 1)), Some((2, 1)));
+
+        *grid.get_mut((1, 1)).unwrap() = 'x';
+        grid[(0, 0)] = 'y';
+        assert_eq!(grid.to_string(), "ybc\ndxf");
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_when_indexing_out_of_bounds() {
+        let _ = get_mock_grid()[(0, 2)];
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            [(2, 1), (1, 2), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).unwrap().collect::<String>(), "be");
+        assert!(grid.column(3).is_none());
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.find(|c| *c == 'e'), Some((1, 1)));
+        assert_eq!(grid.find(|c| *c == 'z'), None);
+        assert_eq!(
+            grid.find_all(|c| "aeiou".contains(*c)).collect::<Vec<_>>(),
+            [(0, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn transforms_grid() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
@@ -1,3 +1,10 @@
+pub mod geometry;
+pub mod grid;
+pub mod math;
+pub mod parse;
+pub mod ranges;
+pub mod scan;
+pub mod search;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -1,25 +1,49 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, leaderboard, read, scaffold, session, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
+use advent_of_code::template::puzzle;
 use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{build_options::BuildOptions, Day};
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
+        },
+        Examples {
+            day: Day,
+            force: bool,
+        },
+        Leaderboard {
+            path: Option<String>,
+            fetch: Option<String>,
+            markdown: Option<String>,
         },
         Read {
             day: Day,
+            offline: bool,
+            part: Option<u8>,
+            new: bool,
         },
+        Login {
+            session: Option<String>,
+        },
+        SessionStatus,
         Scaffold {
             day: Day,
             download: bool,
+            template: Option<String>,
+            dir: bool,
+            no_editor: bool,
+            force: bool,
         },
         Solve {
             day: Day,
@@ -27,17 +51,35 @@ mod args {
             time: bool,
             dhat: bool,
             submit: Option<u8>,
+            options: BuildOptions,
         },
         All {
             release: bool,
             time: bool,
+            dhat: bool,
+            options: BuildOptions,
         },
         Time {
             all: bool,
             day: Option<Day>,
+            options: BuildOptions,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            force: bool,
+        },
+    }
+
+    /// Parses the options that `solve`, `all` and `time` pass through to cargo.
+    fn parse_build_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BuildOptions, pico_args::Error> {
+        Ok(BuildOptions {
+            features: args.opt_value_from_str("--features")?,
+            profile: args.opt_value_from_str("--profile")?,
+            target_dir: args.opt_value_from_str("--target-dir")?,
+            native: args.contains("--native"),
+        })
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -47,34 +89,88 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                dhat: args.contains("--dhat"),
+                options: parse_build_options(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
+                let options = parse_build_options(&mut args)?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
+                    options,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
             },
-            Some("read") => AppArguments::Read {
+            Some("examples") => AppArguments::Examples {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("leaderboard") => {
+                let fetch = args.opt_value_from_str("--fetch")?;
+                let markdown = args.opt_value_from_str("--markdown")?;
+
+                AppArguments::Leaderboard {
+                    path: args.opt_free_from_str()?,
+                    fetch,
+                    markdown,
+                }
+            }
+            Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
-                download: args.contains("--download"),
+                offline: args.contains("--offline"),
+                part: args.opt_value_from_str("--part")?,
+                new: args.contains("--new"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-                dhat: args.contains("--dhat"),
+            Some("login") => AppArguments::Login {
+                session: args.opt_free_from_str()?,
             },
+            Some("session") => match args.subcommand()?.as_deref() {
+                Some("login") => AppArguments::Login {
+                    session: args.opt_free_from_str()?,
+                },
+                Some("status") | None => AppArguments::SessionStatus,
+                Some(x) => {
+                    eprintln!("Unknown session command: {x}. Expected `login` or `status`.");
+                    process::exit(1);
+                }
+            },
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let template = args.opt_value_from_str("--template")?;
+                let dir = args.contains("--dir");
+                let no_editor = args.contains("--no-editor");
+                let force = args.contains("--force");
+
+                AppArguments::Scaffold {
+                    day: args.free_from_str()?,
+                    download,
+                    template,
+                    dir,
+                    no_editor,
+                    force,
+                }
+            }
+            Some("solve") => {
+                let options = parse_build_options(&mut args)?;
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    time: args.contains("--time"),
+                    dhat: args.contains("--dhat"),
+                    options,
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                force: args.contains("--force"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -94,6 +190,15 @@ mod args {
     }
 }
 
+/// Downloads input and puzzle as the first step of `scaffold --download` and `today`.
+/// Downloading first lets the template use the puzzle title. A failed download doesn't stop the remaining steps.
+fn download_in_chain(day: Day, force: bool) {
+    if let Err(e) = download::download(day, force) {
+        eprintln!("{e}");
+        eprintln!("Continuing without the download, run `cargo download {day}` to retry.");
+    }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -101,15 +206,40 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Time { day, all } => time::handle(day, all),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All {
+                release,
+                time,
+                dhat,
+                options,
+            } => all::handle(release, time, dhat, &options),
+            AppArguments::Time { day, all, options } => time::handle(day, all, &options),
+            AppArguments::Download { day, force } => download::handle(day, force),
+            AppArguments::Examples { day, force } => examples::handle(day, force),
+            AppArguments::Leaderboard {
+                path,
+                fetch,
+                markdown,
+            } => leaderboard::handle(path, fetch, markdown),
+            AppArguments::Read {
+                day,
+                offline,
+                part,
+                new,
+            } => read::handle(day, offline, part, new),
+            AppArguments::Login { session } => session::login(session),
+            AppArguments::SessionStatus => session::status(),
+            AppArguments::Scaffold {
+                day,
+                download,
+                template,
+                dir,
+                no_editor,
+                force,
+            } => {
                 if download {
-                    download::handle(day);
+                    download_in_chain(day, force);
                 }
+                scaffold::handle(day, template, dir, no_editor, force);
             }
             AppArguments::Solve {
                 day,
@@ -117,14 +247,17 @@ fn main() {
                 time,
                 dhat,
                 submit,
-            } => solve::handle(day, release, time, dhat, submit),
+                options,
+            } => solve::handle(day, release, time, dhat, submit, &options),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
+            AppArguments::Today { force } => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        download_in_chain(day, force);
+                        scaffold::handle(day, None, false, false, force);
+                        // the puzzle was just downloaded, so there is no need to fetch it again.
+                        let offline = puzzle::read_puzzle(day).is_some();
+                        read::handle(day, offline, None, false)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,379 @@
+//! Number theory helpers: gcd and lcm, extended Euclid, modular arithmetic and the Chinese Remainder Theorem.
+//!
+//! Everything is generic over the primitive integer types. Helpers whose result can exceed the type, like [`lcm`]
+//! and [`crt`], return `None` on overflow instead of wrapping, and [`mod_mul`] and [`mod_pow`] never overflow for
+//! any modulus that fits the type.
+//!
+//! ```ignore
+//! # use advent_of_code::math::{crt, lcm_all};
+//! // ghosts that reach an end node every 2 and every 3 steps meet after 6 steps.
+//! assert_eq!(lcm_all([2u64, 3]), Some(6));
+//! // x = 2 (mod 3), x = 3 (mod 4) and x = 1 (mod 10).
+//! assert_eq!(crt([(2i64, 3), (3, 4), (1, 10)]), Some((11, 60)));
+//! ```
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// The primitive integer types.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// `self % rhs`, without panicking for `MIN % -1`.
+    fn wrapping_rem(self, rhs: Self) -> Self;
+    /// The absolute value, `None` for `MIN` of a signed type.
+    fn checked_abs(self) -> Option<Self>;
+    /// The remainder in `0..rhs.abs()`.
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+/// The signed primitive integer types, needed wherever Bézout coefficients come into play.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($abs:ident => $($ty:ty),*) => {
+        $(
+            impl Integer for $ty {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_mul(self, rhs)
+                }
+
+                fn wrapping_rem(self, rhs: Self) -> Self {
+                    <$ty>::wrapping_rem(self, rhs)
+                }
+
+                fn checked_abs(self) -> Option<Self> {
+                    impl_integer!(@$abs self)
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$ty>::rem_euclid(self, rhs)
+                }
+            }
+        )*
+    };
+    (@signed $value:ident) => { $value.checked_abs() };
+    (@unsigned $value:ident) => { Some($value) };
+}
+
+impl_integer!(signed => i8, i16, i32, i64, i128, isize);
+impl_integer!(unsigned => u8, u16, u32, u64, u128, usize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+/// The greatest common divisor of `a` and `b`, always non-negative. `gcd(0, 0)` is `0`.
+///
+/// # Panics
+/// If the result does not fit, which only happens for `gcd(MIN, 0)`, `gcd(0, MIN)` and `gcd(MIN, MIN)` of a signed
+/// type.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a.wrapping_rem(b));
+    }
+    a.checked_abs().expect("gcd overflows the integer type")
+}
+
+/// The least common multiple of `a` and `b`, always non-negative. `None` if it does not fit into `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).checked_mul(b)?.checked_abs()
+}
+
+/// The greatest common divisor of all `values`, `0` if there are none.
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The least common multiple of all `values`, `1` if there are none. `None` if it does not fit into `T`.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |acc, value| lcm(acc, value))
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+///
+/// The coefficients are the minimal ones, `|x| <= |b / g|` and `|y| <= |a / g|`, so they fit even for `MIN`.
+///
+/// # Panics
+/// Like [`gcd`], if `g` does not fit.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+
+    // stop as soon as `r1` divides `r0`. The step after that would divide `MIN` by `-1` and compute the coefficients
+    // `b / g` and `a / g`, neither of which has to fit.
+    while r1 != T::ZERO {
+        let r = r0.wrapping_rem(r1);
+        if r == T::ZERO {
+            (r0, x0, y0) = (r1, x1, y1);
+            break;
+        }
+
+        let q = r0 / r1;
+        (r0, r1) = (r1, r);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < T::ZERO {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`. `None` if `a` and `m` are not coprime or `m` is not positive.
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+    if m <= T::ZERO {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == T::ONE).then(|| x.rem_euclid(m))
+}
+
+/// `a + b` modulo `m` for `a` and `b` in `0..m`, without overflowing.
+fn mod_add<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `a * b` modulo `m`, in `0..m`, without overflowing for any positive `m`.
+///
+/// # Panics
+/// If `m` is not positive.
+pub fn mod_mul<T: Integer>(a: T, b: T, m: T) -> T {
+    assert!(m > T::ZERO, "the modulus must be positive");
+    let (a, mut b) = (a.rem_euclid(m), b.rem_euclid(m));
+
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    // double and add over the bits of `a`, keeping every intermediate value below `m`.
+    let two = T::ONE + T::ONE;
+    let mut a = a;
+    let mut result = T::ZERO;
+    while a > T::ZERO {
+        if a % two == T::ONE {
+            result = mod_add(result, b, m);
+        }
+        b = mod_add(b, b, m);
+        a = a / two;
+    }
+    result
+}
+
+/// `base` to the power of `exp` modulo `m`, in `0..m`, by repeated squaring.
+///
+/// # Panics
+/// If `m` is not positive or `exp` is negative.
+pub fn mod_pow<T: Integer>(base: T, mut exp: T, m: T) -> T {
+    assert!(exp >= T::ZERO, "the exponent must not be negative");
+    let two = T::ONE + T::ONE;
+    let mut base = base.rem_euclid(m);
+    let mut result = T::ONE % m;
+
+    while exp > T::ZERO {
+        if exp % two == T::ONE {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp = exp / two;
+    }
+    result
+}
+
+/// Solves a system of congruences `x = a (mod m)` given as `(a, m)` pairs with the Chinese Remainder Theorem.
+///
+/// The moduli don't need to be coprime. Returns `(x, l)` where `l` is the lcm of all moduli and `x` the smallest
+/// non-negative solution; every solution is `x + k * l`. An empty system is solved by `(0, 1)`.
+///
+/// `None` if the congruences contradict each other, a modulus is not positive, or `l` does not fit into `T`.
+pub fn crt<T: Signed>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::ZERO, T::ONE), |(a1, m1), (a2, m2)| {
+            if m2 <= T::ZERO {
+                return None;
+            }
+            let a2 = a2.rem_euclid(m2);
+
+            // x = a1 + m1 * k must satisfy m1 * k = a2 - a1 (mod m2), which needs g | a2 - a1.
+            let g = gcd(m1, m2);
+            let diff = a2 - a1;
+            if diff % g != T::ZERO {
+                return None;
+            }
+
+            let modulus = m2 / g;
+            let l = m1.checked_mul(modulus)?;
+            let inverse = mod_inverse(m1 / g, modulus)?;
+            let k = mod_mul((diff / g).rem_euclid(modulus), inverse, modulus);
+
+            // k < m2 / g, so both the product and the sum stay below l.
+            Some((a1 + m1 * k, l))
+        })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, extended_gcd, gcd, gcd_all, lcm, lcm_all, mod_inverse, mod_mul, mod_pow};
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(gcd(i64::MIN, 3), 1);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(-4i32, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(200u8, 3), None);
+
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all::<u32>([]), 0);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all::<u32>([]), Some(1));
+        assert_eq!(lcm_all([u64::MAX, 2]), None);
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(i32::MIN, -1), (1, 0, -1));
+        assert_eq!(extended_gcd(3, i64::MIN), (1, 3074457345618258603, 1));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 12), None);
+        assert_eq!(mod_pow(2u64, 10, 1000), 24);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(mod_mul(-1i8, -1, 127), 1);
+        assert_eq!(mod_mul(u128::MAX - 1, u128::MAX - 1, u128::MAX), 1);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1i32, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1i32, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1i32, 5)]), Some((4, 5)));
+        assert_eq!(crt::<i32>([]), Some((0, 1)));
+        assert_eq!(crt([(0i32, 0)]), None);
+        assert_eq!(crt([(0i8, 11), (0, 13)]), None);
+    }
+
+    // proptest is a dev-dependency, so these only exist in test builds.
+    #[cfg(test)]
+    mod properties {
+        use crate::math::{crt, extended_gcd, gcd, lcm, lcm_all, mod_inverse, mod_mul, mod_pow};
+        use proptest::prelude::*;
+
+        /// Any `i64`, with the values that tend to overflow showing up far more often than by chance.
+        fn extremes() -> impl Strategy<Value = i64> {
+            prop_oneof![
+                Just(i64::MIN),
+                Just(i64::MIN + 1),
+                Just(i64::MAX),
+                Just(-1),
+                Just(0),
+                Just(1),
+                any::<i64>(),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn gcd_divides_both(a in any::<i64>(), b in any::<i64>()) {
+                prop_assume!(a != i64::MIN && b != i64::MIN);
+                let g = gcd(a, b);
+                prop_assert!(g >= 0);
+                if g != 0 {
+                    prop_assert_eq!((a % g, b % g), (0, 0));
+                    prop_assert_eq!(gcd(a / g, b / g), 1);
+                }
+            }
+
+            #[test]
+            fn lcm_is_a_common_multiple(a in 1u32..100_000, b in 1u32..100_000) {
+                let l = lcm(a as u64, b as u64).unwrap();
+                prop_assert_eq!((l % a as u64, l % b as u64), (0, 0));
+                prop_assert_eq!(l * gcd(a, b) as u64, a as u64 * b as u64);
+            }
+
+            #[test]
+            fn bezout_identity_holds(a in extremes(), b in extremes()) {
+                // the only inputs whose gcd is `2^63`.
+                prop_assume!(![(i64::MIN, 0), (0, i64::MIN), (i64::MIN, i64::MIN)].contains(&(a, b)));
+                let (g, x, y) = extended_gcd(a, b);
+                prop_assert_eq!(g, gcd(a, b));
+                prop_assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+                if g != 0 {
+                    prop_assert!(x.unsigned_abs() <= (b / g).unsigned_abs().max(1));
+                    prop_assert!(y.unsigned_abs() <= (a / g).unsigned_abs().max(1));
+                }
+            }
+
+            #[test]
+            fn inverse_multiplies_to_one(a in any::<i64>(), m in 2i64..i64::MAX) {
+                match mod_inverse(a, m) {
+                    Some(inverse) => prop_assert_eq!(mod_mul(a, inverse, m), 1),
+                    None => prop_assert!(gcd(a.rem_euclid(m), m) != 1),
+                }
+            }
+
+            #[test]
+            fn mod_pow_matches_wide_arithmetic(base in any::<u64>(), exp in 0u64..200, m in 1u64..) {
+                let mut expected = 1 % m as u128;
+                for _ in 0..exp {
+                    expected = expected * base as u128 % m as u128;
+                }
+                prop_assert_eq!(mod_pow(base, exp, m) as u128, expected);
+            }
+
+            #[test]
+            fn crt_satisfies_every_congruence(
+                congruences in prop::collection::vec((any::<i16>(), 1i64..40), 0..5)
+            ) {
+                let congruences: Vec<(i64, i64)> = congruences.into_iter().map(|(a, m)| (a as i64, m)).collect();
+                let l = lcm_all(congruences.iter().map(|&(_, m)| m)).unwrap();
+                let brute_force = (0..l)
+                    .find(|x| congruences.iter().all(|&(a, m)| (x - a).rem_euclid(m) == 0));
+
+                match crt(congruences.iter().copied()) {
+                    Some((x, modulus)) => {
+                        prop_assert_eq!(Some(x), brute_force);
+                        prop_assert_eq!(modulus, l);
+                    }
+                    None => prop_assert_eq!(brute_force, None),
+                }
+            }
+        }
+    }
+}
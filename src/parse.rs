@@ -0,0 +1,331 @@
+//! Helpers for the most common shapes of puzzle input.
+//!
+//! All helpers return a [`ParseError`] that names the offending text instead of panicking, so solutions can decide
+//! whether to `?` or `unwrap` them.
+//!
+//! ```ignore
+//! # use advent_of_code::parse::{blocks, unsigned_ints};
+//! let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n";
+//! let [seeds, _maps] = blocks(input)[..] else { panic!() };
+//! assert_eq!(unsigned_ints::<u64>(seeds).unwrap(), [79, 14]);
+//! ```
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// `text` could not be parsed into the requested type.
+    Invalid { text: String, reason: String },
+    /// `text` does not contain `separator`.
+    MissingSeparator { text: String, separator: String },
+    /// `line` is shorter than the widths of its columns.
+    TooShort { line: String, expected: usize },
+    /// No section with this label exists.
+    MissingSection(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Invalid { text, reason } => {
+                write!(f, "could not parse `{text}`: {reason}.")
+            }
+            ParseError::MissingSeparator { text, separator } => {
+                write!(f, "expected `{separator}` in `{text}`.")
+            }
+            ParseError::TooShort { line, expected } => write!(
+                f,
+                "expected at least {expected} characters, but `{line}` has {}.",
+                line.chars().count()
+            ),
+            ParseError::MissingSection(label) => write!(f, "no section labelled `{label}`."),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `text` with [`FromStr`], naming `text` in the error.
+pub fn parse_value<T>(text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.trim()
+        .parse()
+        .map_err(|e: T::Err| ParseError::Invalid {
+            text: text.trim().into(),
+            reason: e.to_string(),
+        })
+}
+
+/// Splits `s` into the runs of digits it contains, keeping a leading `-` if `signed`.
+fn number_tokens(s: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+
+        let start = if signed && i > 0 && bytes[i - 1] == b'-' {
+            i - 1
+        } else {
+            i
+        };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        Some(&s[start..i])
+    })
+}
+
+/// Extracts all integers from `s`, treating a `-` right before a number as its sign.
+/// Everything else, including other separators, is skipped: `"x=-3, y=10"` yields `[-3, 10]`.
+/// Note that ranges like `"1-5"` yield `[1, -5]`; use [`unsigned_ints`] for those.
+pub fn signed_ints<T>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    number_tokens(s, true).map(parse_value).collect()
+}
+
+/// Extracts all runs of digits from `s` as numbers, ignoring any `-`: `"1-5"` yields `[1, 5]`.
+pub fn unsigned_ints<T>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    number_tokens(s, false).map(parse_value).collect()
+}
+
+/// Splits `input` into blocks that are separated by one or more blank lines.
+/// Leading and trailing blank lines are ignored, and `\r\n` line endings are supported.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let is_blank = line.trim().is_empty();
+
+        match (is_blank, start) {
+            (false, None) => start = Some(offset),
+            (true, Some(s)) => {
+                blocks.push(&input[s..end]);
+                start = None;
+            }
+            _ => {}
+        }
+
+        offset += line.len();
+        if !is_blank {
+            end = offset - (line.len() - line.trim_end_matches(['\n', '\r']).len());
+        }
+    }
+
+    if let Some(s) = start {
+        blocks.push(&input[s..end]);
+    }
+
+    blocks
+}
+
+/// Parses a list of `key=value` pairs separated by `separator`, e.g. `x=787,m=2655` with `','`.
+/// Surrounding braces, as in `{x=787,m=2655}`, are ignored, and values are parsed into `T`.
+pub fn key_values<T>(s: &str, separator: char) -> Result<Vec<(&str, T)>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let s = s.trim();
+    let s = s
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .unwrap_or(s);
+
+    s.split(separator)
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) =
+                pair.split_once('=')
+                    .ok_or_else(|| ParseError::MissingSeparator {
+                        text: pair.into(),
+                        separator: "=".into(),
+                    })?;
+            Ok((key.trim(), parse_value(value)?))
+        })
+        .collect()
+}
+
+/// Splits `line` into columns of the given `widths`, trimming each column.
+/// The last column may be shorter than its width, any text after it is ignored.
+pub fn fixed_width<'a>(line: &'a str, widths: &[usize]) -> Result<Vec<&'a str>, ParseError> {
+    let mut columns = Vec::with_capacity(widths.len());
+    let mut rest = line;
+
+    for (i, &width) in widths.iter().enumerate() {
+        let is_last = i + 1 == widths.len();
+        let split = match rest.char_indices().nth(width) {
+            Some((index, _)) => index,
+            None if is_last || rest.chars().count() == width => rest.len(),
+            None => {
+                return Err(ParseError::TooShort {
+                    line: line.into(),
+                    expected: widths[..=i].iter().sum(),
+                })
+            }
+        };
+
+        let (column, remainder) = rest.split_at(split);
+        columns.push(column.trim());
+        rest = remainder;
+    }
+
+    Ok(columns)
+}
+
+/// A block of the input that starts with a label, like `seed-to-soil map:` or `Time: 7 15 30`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The text before the first `:`.
+    pub label: &'a str,
+    /// Everything after the first `:`, trimmed. Can span several lines.
+    pub body: &'a str,
+}
+
+/// Splits `input` into labelled sections. Each section is either a blank-line separated block or a single line,
+/// depending on `per_line`.
+pub fn sections(input: &str, per_line: bool) -> Result<Vec<Section<'_>>, ParseError> {
+    let parts: Vec<&str> = if per_line {
+        input.lines().filter(|l| !l.trim().is_empty()).collect()
+    } else {
+        blocks(input)
+    };
+
+    parts
+        .into_iter()
+        .map(|part| {
+            let (label, body) =
+                part.split_once(':')
+                    .ok_or_else(|| ParseError::MissingSeparator {
+                        text: part.lines().next().unwrap_or_default().into(),
+                        separator: ":".into(),
+                    })?;
+            Ok(Section {
+                label: label.trim(),
+                body: body.trim(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the body of the section labelled `label`. See [`sections`].
+pub fn section<'a>(sections: &[Section<'a>], label: &str) -> Result<&'a str, ParseError> {
+    sections
+        .iter()
+        .find(|section| section.label == label)
+        .map(|section| section.body)
+        .ok_or_else(|| ParseError::MissingSection(label.into()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        blocks, fixed_width, key_values, section, sections, signed_ints, unsigned_ints, ParseError,
+        Section,
+    };
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(signed_ints::<i32>("x=-3, y=10..-7").unwrap(), [-3, 10, -7]);
+        assert_eq!(unsigned_ints::<u32>("1-5 a: 12").unwrap(), [1, 5, 12]);
+        assert_eq!(signed_ints::<i64>("1-5").unwrap(), [1, -5]);
+        assert_eq!(signed_ints::<i32>("no numbers").unwrap(), []);
+        assert_eq!(
+            unsigned_ints::<u8>("1 300"),
+            Err(ParseError::Invalid {
+                text: "300".into(),
+                reason: "number too large to fit in target type".into()
+            })
+        );
+        assert!(unsigned_ints::<u8>("-1").is_ok());
+        assert!(signed_ints::<u8>("-1").is_err());
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "\na\nb\n\n\nc\r\n\r\nd\n\n";
+        assert_eq!(blocks(input), ["a\nb", "c", "d"]);
+        assert_eq!(blocks("a"), ["a"]);
+        assert_eq!(blocks(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn parses_key_values() {
+        assert_eq!(
+            key_values::<u32>("{x=787,m=2655,a=1222,s=2876}", ',').unwrap(),
+            [("x", 787), ("m", 2655), ("a", 1222), ("s", 2876)]
+        );
+        assert_eq!(
+            key_values::<String>("a = b; c=d;", ';').unwrap(),
+            [("a", "b".to_string()), ("c", "d".to_string())]
+        );
+
+        let error = key_values::<u32>("x=1,m", ',').unwrap_err();
+        assert_eq!(error.to_string(), "expected `=` in `m`.");
+        assert!(key_values::<u32>("x=a", ',').is_err());
+    }
+
+    #[test]
+    fn splits_fixed_width_columns() {
+        assert_eq!(
+            fixed_width("[A] [B]     [D]", &[4, 4, 4, 4]).unwrap(),
+            ["[A]", "[B]", "", "[D]"]
+        );
+        assert_eq!(fixed_width("ab", &[1, 5]).unwrap(), ["a", "b"]);
+        assert_eq!(
+            fixed_width("ab", &[3, 1]),
+            Err(ParseError::TooShort {
+                line: "ab".into(),
+                expected: 3
+            })
+        );
+    }
+
+    #[test]
+    fn parses_sections() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        let parsed = sections(input, false).unwrap();
+        assert_eq!(
+            parsed,
+            [
+                Section {
+                    label: "seeds",
+                    body: "79 14"
+                },
+                Section {
+                    label: "seed-to-soil map",
+                    body: "50 98 2\n52 50 48"
+                }
+            ]
+        );
+        assert_eq!(section(&parsed, "seeds").unwrap(), "79 14");
+        assert_eq!(
+            section(&parsed, "soil-to-water map"),
+            Err(ParseError::MissingSection("soil-to-water map".into()))
+        );
+
+        let parsed = sections("Time: 7 15\nDistance: 9 40\n", true).unwrap();
+        assert_eq!(section(&parsed, "Distance").unwrap(), "9 40");
+        assert!(sections("no label", true).is_err());
+    }
+}
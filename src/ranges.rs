@@ -0,0 +1,353 @@
+//! Sets of half-open ranges and piecewise-linear maps between them.
+//!
+//! Puzzles like "map every seed in these ranges through a chain of tables" or "count the combinations of four
+//! ratings that pass a workflow" are hopeless value by value, but only touch a handful of ranges when whole ranges
+//! are split and shifted at once.
+//!
+//! ```ignore
+//! # use advent_of_code::ranges::{RangeMap, RangeSet};
+//! let seeds = RangeSet::from_iter([79..93, 55..68]);
+//! let soil = RangeMap::from_iter([(98..100, 50), (50..98, 52)]);
+//! assert_eq!(soil.map_set(&seeds).ranges(), [57..70, 81..95]);
+//! ```
+use std::iter::Sum;
+use std::ops::{Add, Range, Sub};
+
+/// A set of values stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts `ranges` and merges the ones that overlap or touch. Empty ranges are dropped.
+    fn normalized(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|range| range.start < range.end);
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        RangeSet { ranges: merged }
+    }
+
+    /// The ranges of the set in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Range<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest value in the set.
+    pub fn first(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// The end of the last range, i.e. one past the largest value in the set.
+    pub fn end(&self) -> Option<T> {
+        self.ranges.last().map(|range| range.end)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        // index of the first range that starts after `value`.
+        let i = self.ranges.partition_point(|range| range.start <= *value);
+        i > 0 && *value < self.ranges[i - 1].end
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+
+    /// Adds all values of `range` to the set.
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalized(ranges);
+    }
+
+    /// All values that are in `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    /// All values that are in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // drop the range that ends first, it can't overlap anything else.
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        RangeSet { ranges }
+    }
+
+    /// All values that are in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            // skip ranges of `other` that end before this range, they can't affect later ranges either.
+            while other.ranges.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+
+            for b in other.ranges[j..].iter().take_while(|b| b.start < range.end) {
+                if start < b.start {
+                    ranges.push(start..b.start);
+                }
+                start = start.max(b.end);
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        RangeSet { ranges }
+    }
+
+    /// Splits the set into the values below `at` and the values from `at` onwards.
+    ///
+    /// A rule like `x<1416` is `split_at(1416)` and yields `(matching, rest)`, `x>2662` is `split_at(2663)` and
+    /// yields `(rest, matching)`.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = vec![];
+        let mut above = vec![];
+
+        for range in &self.ranges {
+            if range.end <= at {
+                below.push(range.clone());
+            } else if range.start >= at {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..at);
+                above.push(at..range.end);
+            }
+        }
+
+        (RangeSet { ranges: below }, RangeSet { ranges: above })
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::normalized(vec![range])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalized(iter.into_iter().collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RangeSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+impl<T> IntoIterator for RangeSet<T> {
+    type Item = Range<T>;
+    type IntoIter = std::vec::IntoIter<Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+/// A piecewise-linear map: every source range is shifted so it starts at its destination, all other values map to
+/// themselves. When source ranges overlap, the one added first wins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    /// Source ranges and the value their start maps to.
+    entries: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        RangeMap { entries: vec![] }
+    }
+}
+
+impl<T> RangeMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates a map that maps every value to itself.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `source` to the range starting at `destination`.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        self.entries.push((source, destination));
+    }
+
+    /// Maps `length` values from `source` to `destination`, in the order of an almanac line like `50 98 2`.
+    pub fn insert_len(&mut self, destination: T, source: T, length: T) {
+        self.insert(source..source + length, destination);
+    }
+
+    /// Maps a single value.
+    pub fn map(&self, value: T) -> T {
+        self.entries
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(source, destination)| {
+                *destination + (value - source.start)
+            })
+    }
+
+    /// Maps every value of `set` at once, splitting its ranges wherever they cross the edge of a source range.
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let mut remaining = set.clone();
+        let mut mapped = vec![];
+
+        for (source, destination) in &self.entries {
+            let source = RangeSet::from(source.clone());
+            for range in remaining.intersection(&source) {
+                let start = *destination + (range.start - source.ranges[0].start);
+                mapped.push(start..start + (range.end - range.start));
+            }
+            remaining = remaining.difference(&source);
+        }
+
+        mapped.extend(remaining);
+        RangeSet::normalized(mapped)
+    }
+}
+
+impl<T> FromIterator<(Range<T>, T)> for RangeMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        RangeMap {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{RangeMap, RangeSet};
+
+    #[test]
+    fn normalizes_ranges() {
+        let set = RangeSet::from_iter([5..8, 1..3, 3..4, 7..10, 12..12]);
+        assert_eq!(set.ranges(), [1..4, 5..10]);
+        assert_eq!(set.len(), 8);
+        assert_eq!((set.first(), set.end()), (Some(1), Some(10)));
+        assert!(set.contains(&3) && set.contains(&9));
+        assert!(!set.contains(&4) && !set.contains(&10) && !set.contains(&0));
+
+        let mut set = RangeSet::new();
+        assert!(set.is_empty());
+        set.insert(4..6);
+        set.insert(0..2);
+        set.insert(1..5);
+        assert_eq!(set.ranges(), [0..6]);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = RangeSet::from_iter([0..5, 10..15, 20..25]);
+        let b = RangeSet::from_iter([3..12, 14..21, 30..31]);
+
+        assert_eq!(a.union(&b).ranges(), [0..25, 30..31]);
+        assert_eq!(a.intersection(&b).ranges(), [3..5, 10..12, 14..15, 20..21]);
+        assert_eq!(a.difference(&b).ranges(), [0..3, 12..14, 21..25]);
+        assert_eq!(b.difference(&a).ranges(), [5..10, 15..20, 30..31]);
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a.difference(&RangeSet::new()), a);
+    }
+
+    #[test]
+    fn splits_sets() {
+        let set = RangeSet::from_iter([1..4001]);
+        let (matching, rest) = set.split_at(1416);
+        assert_eq!((matching.len(), rest.len()), (1415, 2585));
+
+        let set = RangeSet::from_iter([0..5, 10..15]);
+        let (below, above) = set.split_at(5);
+        assert_eq!(
+            (below.ranges(), above.ranges()),
+            (&[0..5][..], &[10..15][..])
+        );
+        let (below, above) = set.split_at(12);
+        assert_eq!(
+            (below.ranges(), above.ranges()),
+            (&[0..5, 10..12][..], &[12..15][..])
+        );
+    }
+
+    #[test]
+    fn maps_ranges() {
+        let mut map = RangeMap::new();
+        map.insert_len(50u64, 98, 2);
+        map.insert_len(52, 50, 48);
+
+        assert_eq!((map.map(79), map.map(99), map.map(10)), (81, 51, 10));
+
+        let seeds = RangeSet::from_iter([79..93, 55..68]);
+        assert_eq!(map.map_set(&seeds).ranges(), [57..70, 81..95]);
+
+        let straddling = RangeSet::from_iter([45..55, 97..101]);
+        assert_eq!(map.map_set(&straddling).ranges(), [45..57, 99..101]);
+    }
+
+    #[test]
+    fn first_entry_wins() {
+        let map = RangeMap::from_iter([(0..10, 100), (5..15, 200)]);
+        assert_eq!((map.map(7), map.map(12)), (107, 207));
+        assert_eq!(
+            map.map_set(&RangeSet::from(0..15)).ranges(),
+            [100..110, 205..210]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Pattern matching for structured lines, see [`scan!`](crate::scan!).
+use std::fmt::{Display, Formatter};
+
+/// The error of a failed [`scan!`](crate::scan!), pointing at the first column of the input that did not match.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScanError {
+    /// The 1-based column of the offending character.
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// Types that a `{}` placeholder can be scanned into.
+///
+/// Implemented for `&str`, `String`, `char`, `bool` and all numeric primitives. For your own types, implement `scan`
+/// by calling their parser and turning its error into a message.
+pub trait Scan<'a>: Sized {
+    fn scan(s: &'a str) -> Result<Self, String>;
+}
+
+impl<'a> Scan<'a> for &'a str {
+    fn scan(s: &'a str) -> Result<Self, String> {
+        Ok(s)
+    }
+}
+
+macro_rules! impl_scan_from_str {
+    ($($ty:ty),*) => {
+        $(
+            impl Scan<'_> for $ty {
+                fn scan(s: &str) -> Result<Self, String> {
+                    s.parse().map_err(|e| format!("{e}"))
+                }
+            }
+        )*
+    };
+}
+
+impl_scan_from_str!(
+    String, char, bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+/// Walks through an input along a pattern. Used by [`scan!`](crate::scan!).
+#[doc(hidden)]
+pub struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+    /// The literal text after each placeholder.
+    literals: Vec<&'static str>,
+    field: usize,
+}
+
+impl<'a> Scanner<'a> {
+    /// Starts scanning `input`, matching the literal text before the first placeholder of `pattern`.
+    pub fn new(input: &'a str, pattern: &'static str) -> Result<Self, ScanError> {
+        let mut literals: Vec<&'static str> = pattern.split("{}").collect();
+        let prefix = literals.remove(0);
+
+        let mut scanner = Scanner {
+            input,
+            pos: 0,
+            literals,
+            field: 0,
+        };
+        scanner.expect(prefix)?;
+        Ok(scanner)
+    }
+
+    fn column(&self, pos: usize) -> usize {
+        self.input[..pos].chars().count() + 1
+    }
+
+    fn error(&self, pos: usize, message: String) -> ScanError {
+        ScanError {
+            column: self.column(pos),
+            message,
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), ScanError> {
+        let rest = &self.input[self.pos..];
+        if let Some(rest) = rest.strip_prefix(literal) {
+            self.pos = self.input.len() - rest.len();
+            return Ok(());
+        }
+
+        // point at the first character that differs.
+        let matching: usize = rest
+            .chars()
+            .zip(literal.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        let found = rest[matching..].chars().next();
+
+        Err(self.error(
+            self.pos + matching,
+            match found {
+                Some(c) => format!("expected `{literal}`, found `{c}`."),
+                None => format!("expected `{literal}`, found the end of the input."),
+            },
+        ))
+    }
+
+    /// Points at where the longest prefix of `literal` stops matching, as that is most likely where the line
+    /// deviates from the pattern.
+    fn missing_literal(&self, literal: &str, name: &str) -> ScanError {
+        let rest = &self.input[self.pos..];
+        let (end, expected) = literal
+            .char_indices()
+            .map(|(k, _)| k)
+            .filter(|&k| k > 0)
+            .rev()
+            .find_map(|k| rest.find(&literal[..k]).map(|i| (i + k, &literal[k..])))
+            .unwrap_or((rest.len(), literal));
+
+        let found = match rest[end..].chars().next() {
+            Some(c) => format!("`{c}`"),
+            None => "the end of the input".into(),
+        };
+        self.error(
+            self.pos + end,
+            format!("expected `{expected}` after `{name}`, found {found}."),
+        )
+    }
+
+    /// Scans the next placeholder into `T`. `name` is used in error messages.
+    pub fn field<T: Scan<'a>>(&mut self, name: &str) -> Result<T, ScanError> {
+        let literal = *self.literals.get(self.field).ok_or_else(|| {
+            self.error(
+                self.pos,
+                format!("the pattern has no placeholder for `{name}`."),
+            )
+        })?;
+        let is_last = self.field + 1 == self.literals.len();
+        self.field += 1;
+
+        let rest = &self.input[self.pos..];
+        let len = match literal {
+            "" if is_last => rest.len(),
+            "" => {
+                return Err(self.error(
+                    self.pos,
+                    format!("placeholders before `{name}` need text between them."),
+                ))
+            }
+            _ => match rest.find(literal) {
+                Some(len) => len,
+                None => return Err(self.missing_literal(literal, name)),
+            },
+        };
+
+        let start = self.pos;
+        let text = &rest[..len];
+        let value = T::scan(text).map_err(|e| {
+            self.error(
+                start,
+                format!(
+                    "could not scan `{text}` into `{name}: {}`: {e}",
+                    std::any::type_name::<T>()
+                ),
+            )
+        })?;
+
+        self.pos += len;
+        self.expect(literal)?;
+        Ok(value)
+    }
+
+    /// Checks that the whole pattern and the whole input were used up.
+    pub fn finish(self) -> Result<(), ScanError> {
+        if self.field < self.literals.len() {
+            return Err(self.error(
+                self.pos,
+                format!(
+                    "the pattern has {} placeholders, but only {} fields were given.",
+                    self.literals.len(),
+                    self.field
+                ),
+            ));
+        }
+
+        match &self.input[self.pos..] {
+            "" => Ok(()),
+            rest => Err(self.error(self.pos, format!("unexpected trailing input `{rest}`."))),
+        }
+    }
+}
+
+/// Matches a line against a pattern with `{}` placeholders and parses each placeholder into the declared type.
+///
+/// Every placeholder matches as little text as possible, up to the literal text that follows it. The whole
+/// line has to match. Returns the fields as a tuple, or a [`ScanError`](crate::scan::ScanError) with the column where
+/// the line deviates from the pattern or a field could not be parsed.
+///
+/// Braces other than `{}` are plain text, so `"{}{{}}"` matches a label followed by a braced field, as in
+/// `hdj{m>838:A,pv}`.
+///
+/// For example, `scan!(line, "{} {} (#{})" => direction: char, steps: u32, color: &str)` turns `R 6 (#70c710)` into
+/// `('R', 6, "70c710")`, and fails with column 3 for `R x (#70c710)`.
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $pattern:literal => $($name:ident : $ty:ty),+ $(,)?) => {
+        (|| -> ::std::result::Result<_, $crate::scan::ScanError> {
+            let mut scanner = $crate::scan::Scanner::new($input, $pattern)?;
+            $( let $name: $ty = scanner.field(stringify!($name))?; )+
+            scanner.finish()?;
+            Ok(($($name,)+))
+        })()
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Scan, ScanError};
+
+    #[derive(Debug, PartialEq)]
+    struct Color(u32);
+
+    impl Scan<'_> for Color {
+        fn scan(s: &str) -> Result<Self, String> {
+            u32::from_str_radix(s, 16)
+                .map(Color)
+                .map_err(|e| e.to_string())
+        }
+    }
+
+    #[test]
+    fn scans_fields() {
+        assert_eq!(
+            crate::scan!("AAA = (BBB, CCC)", "{} = ({}, {})" => a: &str, b: &str, c: &str),
+            Ok(("AAA", "BBB", "CCC"))
+        );
+        assert_eq!(
+            crate::scan!("R 6 (#70c710)", "{} {} (#{})" => d: char, n: u8, c: Color),
+            Ok(('R', 6, Color(0x0070_c710)))
+        );
+        assert_eq!(
+            crate::scan!("hdj{m>838:A,pv}", "{}{{}}" => name: String, rules: &str),
+            Ok(("hdj".to_string(), "m>838:A,pv"))
+        );
+        assert_eq!(
+            crate::scan!("x=-3, y=1.5", "x={}, y={}" => x: i32, y: f64),
+            Ok((-3, 1.5))
+        );
+    }
+
+    #[test]
+    fn reports_offending_column() {
+        let error = crate::scan!("AAA = [BBB, CCC)", "{} = ({}, {})" => a: &str, b: &str, c: &str)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            ScanError {
+                column: 7,
+                message: "expected `(` after `a`, found `[`.".into()
+            }
+        );
+
+        let error =
+            crate::scan!("R -6 (#70c710)", "{} {} (#{})" => d: char, n: u32, c: &str).unwrap_err();
+        assert_eq!(error.column, 3);
+        assert!(error
+            .to_string()
+            .starts_with("column 3: could not scan `-6` into `n: u32`"));
+
+        let error =
+            crate::scan!("R x (#70c710)", "{} {} (#{})" => d: char, n: u32, c: &str).unwrap_err();
+        assert_eq!(error.column, 3);
+
+        let error =
+            crate::scan!("R 6 (#xyz)", "{} {} (#{})" => d: char, n: u32, c: Color).unwrap_err();
+        assert_eq!(error.column, 7);
+    }
+
+    #[test]
+    fn rejects_incomplete_lines() {
+        let error =
+            crate::scan!("a = (b, c", "{} = ({}, {})" => a: &str, b: &str, c: &str).unwrap_err();
+        assert_eq!(error.column, 10);
+
+        let error = crate::scan!("a = b!", "{} = {}" => a: &str, b: char).unwrap_err();
+        assert_eq!(error.column, 5);
+
+        let error = crate::scan!("1 2 3", "{} {}" => a: u8, b: u8).unwrap_err();
+        assert_eq!(error.column, 3);
+
+        let error = crate::scan!("1 2", "{} {}" => a: u8).unwrap_err();
+        assert_eq!(error.column, 3);
+    }
+}
@@ -0,0 +1,449 @@
+//! Generic graph search: breadth-first search, Dijkstra and A*.
+//!
+//! The graph is never built explicitly. A search starts at a state and asks a `successors` closure for the neighbours
+//! of every state it visits, so states can be anything hashable: grid positions, `(position, direction)` pairs or
+//! whole puzzle configurations.
+//!
+//! ```ignore
+//! # use advent_of_code::search::bfs;
+//! // count up from 1 to 10 by doubling or adding one.
+//! let result = bfs(1, |n| [n * 2, n + 1], |n| *n == 10);
+//! assert_eq!(result.cost(), Some(4));
+//! assert_eq!(result.path(), Some(vec![1, 2, 4, 5, 10]));
+//! ```
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The outcome of a search: the goal it reached, if any, and the cost and shortest-path predecessors of every
+/// state it visited.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S, C> {
+    start: S,
+    goal: Option<S>,
+    costs: HashMap<S, C>,
+    /// All predecessors of a state on its shortest paths.
+    parents: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> SearchResult<S, C> {
+    fn new(start: S, zero: C) -> Self {
+        SearchResult {
+            costs: HashMap::from([(start.clone(), zero)]),
+            parents: HashMap::new(),
+            goal: None,
+            start,
+        }
+    }
+
+    /// The goal that was reached first, `None` if no goal is reachable.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of a shortest path to the goal.
+    pub fn cost(&self) -> Option<C> {
+        self.cost_to(self.goal.as_ref()?)
+    }
+
+    /// The cost of a shortest path to `state`, `None` if it was not visited.
+    pub fn cost_to(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// A shortest path from the start to the goal, including both.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// A shortest path from the start to `state`, including both. `None` if `state` was not visited.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.costs.contains_key(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self
+            .parents
+            .get(path.last()?)
+            .and_then(|parents| parents.first())
+        {
+            path.push(parent.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// All shortest paths from the start to `state`.
+    /// The number of paths can grow exponentially, prefer [`SearchResult::on_shortest_paths`] where possible.
+    pub fn all_paths_to(&self, state: &S) -> Vec<Vec<S>> {
+        if !self.costs.contains_key(state) {
+            return vec![];
+        }
+
+        match self.parents.get(state) {
+            None => vec![vec![state.clone()]],
+            Some(parents) => parents
+                .iter()
+                .flat_map(|parent| self.all_paths_to(parent))
+                .map(|mut path| {
+                    path.push(state.clone());
+                    path
+                })
+                .collect(),
+        }
+    }
+
+    /// All states that lie on at least one shortest path from the start to `state`, including both.
+    pub fn on_shortest_paths(&self, state: &S) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        if !self.costs.contains_key(state) {
+            return seen;
+        }
+
+        let mut stack = vec![state.clone()];
+        while let Some(current) = stack.pop() {
+            if let Some(parents) = self.parents.get(&current) {
+                stack.extend(parents.iter().filter(|p| !seen.contains(*p)).cloned());
+            }
+            seen.insert(current);
+        }
+
+        seen
+    }
+
+    /// The state the search started from.
+    pub fn start(&self) -> &S {
+        &self.start
+    }
+
+    /// Whether the search reached `state`.
+    pub fn is_visited(&self, state: &S) -> bool {
+        self.costs.contains_key(state)
+    }
+
+    /// All states the search reached, with the cost of their shortest path.
+    pub fn visited(&self) -> impl Iterator<Item = (&S, C)> {
+        self.costs.iter().map(|(state, cost)| (state, *cost))
+    }
+
+    /// The number of states the search reached.
+    pub fn visited_count(&self) -> usize {
+        self.costs.len()
+    }
+
+    fn add_parent(&mut self, state: &S, parent: S, replace: bool) {
+        let parents = self.parents.entry(state.clone()).or_default();
+        if replace {
+            parents.clear();
+        }
+        parents.push(parent);
+    }
+}
+
+/// Breadth-first search from `start` until a state matches `is_goal`.
+/// Every step costs one. Pass `|_| false` as `is_goal` to visit every reachable state.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult::new(start.clone(), 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, cost)) = queue.pop_front() {
+        // every predecessor on a shortest path was dequeued before `state`, so its parents are complete.
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        for next in successors(&state) {
+            match result.costs.get(&next) {
+                None => {
+                    result.costs.insert(next.clone(), cost + 1);
+                    result.add_parent(&next, state.clone(), true);
+                    queue.push_back((next, cost + 1));
+                }
+                Some(&known) if known == cost + 1 => result.add_parent(&next, state.clone(), false),
+                Some(_) => {}
+            }
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm from `start` until a state matches `is_goal`.
+/// `successors` returns the neighbours of a state together with the non-negative cost of moving there.
+/// Zero-cost edges are allowed as long as they don't form a cycle.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// A* search from `start` until a state matches `is_goal`.
+/// `heuristic` estimates the remaining cost to a goal. It must never overestimate it (e.g. the Manhattan distance
+/// on a grid), and should be consistent for [`SearchResult::cost_to`] to be exact for states other than the goal.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut result = SearchResult::new(start.clone(), C::default());
+    let mut heap = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        state: start,
+    }]);
+
+    while let Some(Entry {
+        priority,
+        cost,
+        state,
+    }) = heap.pop()
+    {
+        // keep going until no cheaper path to the goal is possible, so all shortest paths are recorded
+        // even with zero-cost edges.
+        if result.cost().is_some_and(|goal_cost| priority > goal_cost) {
+            break;
+        }
+
+        // skip stale entries for states that were reached more cheaply in the meantime.
+        if result.costs.get(&state).is_some_and(|known| cost > *known) {
+            continue;
+        }
+
+        if result.goal.is_none() && is_goal(&state) {
+            result.goal = Some(state);
+            continue;
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            match result.costs.get(&next).copied() {
+                Some(known) if next_cost > known => {}
+                Some(known) if next_cost == known => result.add_parent(&next, state.clone(), false),
+                _ => {
+                    result.costs.insert(next.clone(), next_cost);
+                    result.add_parent(&next, state.clone(), true);
+                    heap.push(Entry {
+                        priority: next_cost + heuristic(&next),
+                        cost: next_cost,
+                        state: next,
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// An entry of the priority queue. Only `priority` is compared, so states don't need to be `Ord`.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    /// Reversed, so the `BinaryHeap` pops the lowest priority first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, dijkstra};
+    use crate::geometry::Point;
+    use crate::grid::Grid;
+    use std::collections::HashMap;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn get_mock_maze() -> (Grid<char>, (usize, usize), (usize, usize)) {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(|c| *c == 'S').unwrap();
+        let end = grid.find(|c| *c == 'E').unwrap();
+        (grid, start, end)
+    }
+
+    fn get_mock_graph() -> HashMap<&'static str, Vec<(&'static str, u32)>> {
+        HashMap::from([
+            ("a", vec![("b", 7), ("c", 9), ("f", 14)]),
+            ("b", vec![("a", 7), ("c", 10), ("d", 15)]),
+            ("c", vec![("a", 9), ("b", 10), ("d", 11), ("f", 2)]),
+            ("d", vec![("b", 15), ("c", 11), ("e", 6)]),
+            ("e", vec![("d", 6), ("f", 9)]),
+            ("f", vec![("a", 14), ("c", 2), ("e", 9)]),
+        ])
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path_in_grid() {
+        let (grid, start, end) = get_mock_maze();
+        let result = bfs(
+            start,
+            |pos| grid.neighbours4(*pos).filter(|n| grid[*n] != '#'),
+            |pos| *pos == end,
+        );
+
+        assert_eq!(result.goal(), Some(&end));
+        assert_eq!(result.cost(), Some(15));
+
+        let path = result.path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path
+            .windows(2)
+            .all(|w| Point::from(w[0]).manhattan(Point::from(w[1])) == 1));
+    }
+
+    #[test]
+    fn bfs_visits_reachable_states() {
+        let (grid, start, _) = get_mock_maze();
+        let result = bfs(
+            start,
+            |pos| grid.neighbours4(*pos).filter(|n| grid[*n] != '#'),
+            |_| false,
+        );
+
+        assert_eq!(result.goal(), None);
+        assert_eq!(result.path(), None);
+        assert_eq!(result.visited_count(), grid.find_all(|c| *c != '#').count());
+        assert!(!result.is_visited(&(3, 0)));
+        assert_eq!(result.cost_to(&(0, 4)), Some(4));
+        assert_eq!(result.start(), &start);
+    }
+
+    #[test]
+    fn finds_all_shortest_paths() {
+        let grid = Grid::new(3, 3, '.');
+        let result = bfs((0, 0), |pos| grid.neighbours4(*pos), |pos| *pos == (2, 2));
+
+        let paths = result.all_paths_to(&(2, 2));
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|path| path.len() == 5));
+        assert_eq!(result.on_shortest_paths(&(2, 2)).len(), 9);
+        assert_eq!(result.on_shortest_paths(&(1, 0)).len(), 2);
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path_in_graph() {
+        let graph = get_mock_graph();
+        let result = dijkstra("a", |node| graph[node].clone(), |node| *node == "e");
+
+        assert_eq!(result.cost(), Some(20));
+        assert_eq!(result.path(), Some(vec!["a", "c", "f", "e"]));
+        assert_eq!(result.cost_to(&"c"), Some(9));
+
+        let unreachable = dijkstra("a", |node| graph[node].clone(), |node| *node == "z");
+        assert_eq!(unreachable.cost(), None);
+        assert_eq!(unreachable.visited_count(), 6);
+    }
+
+    #[test]
+    fn dijkstra_records_equal_cost_paths() {
+        let graph = HashMap::from([
+            ("a", vec![("b", 1), ("c", 1)]),
+            ("b", vec![("d", 0)]),
+            ("c", vec![("d", 1)]),
+            ("d", vec![("e", 1)]),
+            ("e", vec![]),
+        ]);
+
+        let result = dijkstra("a", |node| graph[node].clone(), |node| *node == "e");
+        assert_eq!(result.cost(), Some(2));
+        assert_eq!(result.all_paths_to(&"e"), vec![vec!["a", "b", "d", "e"]]);
+
+        let graph = HashMap::from([
+            ("a", vec![("b", 1), ("c", 1)]),
+            ("b", vec![("d", 1)]),
+            ("c", vec![("d", 1)]),
+            ("d", vec![]),
+        ]);
+        let result = dijkstra("a", |node| graph[node].clone(), |node| *node == "d");
+        assert_eq!(result.all_paths_to(&"d").len(), 2);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra_on_weighted_grid() {
+        let grid = Grid::parse("1163751\n1381373\n2136511\n3694931\n7463417", |c| {
+            c.to_digit(10).unwrap()
+        })
+        .unwrap();
+        let end = (grid.width() - 1, grid.height() - 1);
+        let successors = |pos: &(usize, usize)| {
+            grid.neighbours4(*pos)
+                .map(|n| (n, grid[n]))
+                .collect::<Vec<_>>()
+        };
+
+        let expected = dijkstra((0, 0), successors, |pos| *pos == end);
+        let result = astar(
+            (0, 0),
+            successors,
+            |pos| {
+                #[allow(clippy::cast_possible_truncation)]
+                let distance = Point::from(*pos).manhattan(Point::from(end)) as u32;
+                distance
+            },
+            |pos| *pos == end,
+        );
+
+        assert_eq!(result.cost(), expected.cost());
+        assert_eq!(
+            result
+                .path()
+                .unwrap()
+                .iter()
+                .skip(1)
+                .map(|pos| grid[*pos])
+                .sum::<u32>(),
+            result.cost().unwrap()
+        );
+        assert!(result.visited_count() <= expected.visited_count());
+    }
+}
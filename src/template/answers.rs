@@ -0,0 +1,67 @@
+/// Module that stores accepted answers in `data/answers/{day}.txt`, one line per part.
+/// A part with a stored answer counts as solved.
+use std::{fs, io};
+
+use crate::template::Day;
+
+#[must_use]
+pub fn get_answers_path(day: Day) -> String {
+    format!("data/answers/{day}.txt")
+}
+
+fn parse_answer(content: &str, part: u8) -> Option<String> {
+    content
+        .lines()
+        .nth(usize::from(part).checked_sub(1)?)
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(String::from)
+}
+
+fn set_answer(content: &str, part: u8, answer: &str) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let index = usize::from(part.max(1)) - 1;
+
+    if lines.len() <= index {
+        lines.resize(index + 1, "");
+    }
+    lines[index] = answer;
+
+    format!("{}\n", lines.join("\n"))
+}
+
+/// Returns the accepted answer for one part of `day`, if any.
+pub fn read_answer(day: Day, part: u8) -> Option<String> {
+    let content = fs::read_to_string(get_answers_path(day)).ok()?;
+    parse_answer(&content, part)
+}
+
+/// Stores the accepted answer for one part of `day`, keeping answers to other parts.
+pub fn store_answer(day: Day, part: u8, answer: &str) -> Result<(), io::Error> {
+    let path = get_answers_path(day);
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    fs::create_dir_all("data/answers")?;
+    fs::write(path, set_answer(&content, part, answer))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_answer, set_answer};
+
+    #[test]
+    fn reads_answers_per_part() {
+        assert_eq!(parse_answer("42\n1337\n", 1), Some("42".into()));
+        assert_eq!(parse_answer("42\n1337\n", 2), Some("1337".into()));
+        assert_eq!(parse_answer("42\n", 2), None);
+        assert_eq!(parse_answer("\n1337\n", 1), None);
+        assert_eq!(parse_answer("42\n", 0), None);
+    }
+
+    #[test]
+    fn stores_answers_per_part() {
+        assert_eq!(set_answer("", 1, "42"), "42\n");
+        assert_eq!(set_answer("42\n", 2, "1337"), "42\n1337\n");
+        assert_eq!(set_answer("", 2, "1337"), "\n1337\n");
+        assert_eq!(set_answer("41\n1337\n", 1, "42"), "42\n1337\n");
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{puzzle, Day};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -46,49 +46,84 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
         day,
     );
 
-    call_aoc_cli(&args)
+    puzzle::keep_previous(day, || call_aoc_cli(&args))
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
+/// Downloads the input and / or the puzzle description for `day`, overwriting existing files.
+pub fn download(day: Day, input: bool, puzzle: bool) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+    let mut args: Vec<String> = vec![
+        "--overwrite".into(),
+        "--input-file".into(),
+        input_path.to_string(),
+        "--puzzle-file".into(),
+        puzzle_path.to_string(),
+    ];
+
+    if !puzzle {
+        args.push("--input-only".into());
+    } else if !input {
+        args.push("--puzzle-only".into());
+    }
+
+    let args = build_args("download", &args, day);
 
-    let output = call_aoc_cli(&args)?;
+    let output = if puzzle {
+        puzzle::keep_previous(day, || call_aoc_cli(&args))?
+    } else {
+        call_aoc_cli(&args)?
+    };
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    if input {
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    }
+    if puzzle {
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    }
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits a result and returns the response printed by aoc-cli.
+pub fn submit(day: Day, part: u8, result: &str) -> Result<String, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // capture stdout so the caller can parse the response.
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    print!("{stdout}");
+
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+/// Returns `true` if `path` exists and is not empty.
+pub(crate) fn has_content(path: &str) -> bool {
+    std::fs::metadata(path).is_ok_and(|m| m.len() > 0)
 }
 
-fn get_input_path(day: Day) -> String {
+pub(crate) fn get_input_path(day: Day) -> String {
     format!("data/inputs/{day}.txt")
 }
 
-fn get_puzzle_path(day: Day) -> String {
+pub(crate) fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub(crate) fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
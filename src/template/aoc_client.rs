@@ -0,0 +1,577 @@
+/// Native client for the Advent of Code website.
+/// Replaces the "aoc-cli" subprocess for downloading inputs, reading puzzles and submitting answers.
+use std::{env, fmt::Display, fs, io, path::PathBuf};
+
+use crate::template::{
+    aoc_cli::{get_input_path, get_puzzle_path, get_year},
+    puzzle, Day,
+};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/fspoettel/advent-of-code-rust)"
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    SessionExpired,
+    YearNotSet,
+    Status(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Paste it into \"~/.adventofcode.session\" or set ADVENT_OF_CODE_SESSION."
+            ),
+            AocClientError::SessionExpired => write!(
+                f,
+                "session expired or invalid. Run `cargo session login` to store a new session cookie."
+            ),
+            AocClientError::YearNotSet => write!(f, "AOC_YEAR is not set."),
+            AocClientError::Status(code) => write!(f, "server responded with status {code}."),
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            // the website answers requests with an expired or malformed cookie with these.
+            ureq::Error::Status(400 | 500, _) => AocClientError::SessionExpired,
+            ureq::Error::Status(code, _) => AocClientError::Status(code),
+            ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+/// Returns `true` if the "aoc-cli" subprocess should be used instead of the native client.
+/// Enabled by setting `AOC_CLIENT=aoc-cli`.
+pub fn use_aoc_cli() -> bool {
+    env::var("AOC_CLIENT").is_ok_and(|x| x == "aoc-cli")
+}
+
+/// Path of the session cookie file, `~/.adventofcode.session`.
+pub fn get_session_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+}
+
+/// Stores the session cookie in `~/.adventofcode.session`, readable only by the current user.
+pub fn store_session(session: &str) -> Result<PathBuf, AocClientError> {
+    let path = get_session_path().ok_or(AocClientError::SessionNotFound)?;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(&path)?;
+    // `mode` only applies to new files, so tighten permissions of an existing file as well.
+    #[cfg(unix)]
+    fs::set_permissions(&path, std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+
+    io::Write::write_all(&mut file, format!("{}\n", session.trim()).as_bytes())?;
+    Ok(path)
+}
+
+/// Returns `true` if a page is the website's login prompt, which it serves instead of content to logged-out users.
+fn is_login_page(html: &str) -> bool {
+    html.contains("/auth/login") || html.contains("Please log in")
+}
+
+fn read_session() -> Result<String, AocClientError> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+
+    let path = get_session_path().ok_or(AocClientError::SessionNotFound)?;
+    let session = fs::read_to_string(path).map_err(|_| AocClientError::SessionNotFound)?;
+    let session = session.trim();
+
+    if session.is_empty() {
+        return Err(AocClientError::SessionNotFound);
+    }
+
+    Ok(session.to_string())
+}
+
+/// HTTP client for a single event year.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            year,
+        }
+    }
+
+    /// Creates a client from the session file and `AOC_YEAR`.
+    /// The base URL can be overridden with `AOC_BASE_URL`, e.g. to point at a local stand-in server.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let year = get_year().ok_or(AocClientError::YearNotSet)?;
+        Ok(Self::new(&base_url, &read_session()?, year))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = ureq::get(url)
+            .set("Cookie", &self.cookie())
+            .set("User-Agent", USER_AGENT)
+            .call()?
+            .into_string()?;
+
+        if is_login_page(&response) {
+            return Err(AocClientError::SessionExpired);
+        }
+        Ok(response)
+    }
+
+    /// Validates the session by requesting a page that is only available to logged-in users.
+    pub fn check_session(&self) -> Result<(), AocClientError> {
+        self.get(&format!(
+            "{}/{}/leaderboard/private",
+            self.base_url, self.year
+        ))
+        .map(|_| ())
+    }
+
+    /// Fetches the puzzle input for `day`.
+    pub fn get_input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetches the puzzle description for `day`, converted to markdown.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(html_to_markdown(main_content(&html)))
+    }
+
+    /// Fetches the JSON export of the private leaderboard with the given `id`.
+    pub fn get_leaderboard(&self, id: &str) -> Result<String, AocClientError> {
+        self.get(&format!(
+            "{}/{}/leaderboard/private/view/{id}.json",
+            self.base_url, self.year
+        ))
+    }
+
+    /// Submits `answer` for one part of `day` and returns the server's message.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let html = ureq::post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        if is_login_page(&html) {
+            return Err(AocClientError::SessionExpired);
+        }
+        Ok(html_to_markdown(main_content(&html)).trim().to_string())
+    }
+}
+
+pub fn read(day: Day) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.get_puzzle(day)?;
+    puzzle::keep_previous(day, || fs::write(get_puzzle_path(day), &puzzle))?;
+    Ok(puzzle)
+}
+
+/// Downloads the input and / or the puzzle description for `day`, overwriting existing files.
+pub fn download(day: Day, input: bool, puzzle: bool) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    if input {
+        fs::write(&input_path, client.get_input(day)?)?;
+    }
+    if puzzle {
+        let content = client.get_puzzle(day)?;
+        puzzle::keep_previous(day, || fs::write(&puzzle_path, content))?;
+    }
+
+    println!("---");
+    if input {
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    }
+    if puzzle {
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    }
+    Ok(())
+}
+
+pub fn submit(day: Day, part: u8, result: &str) -> Result<String, AocClientError> {
+    AocClient::from_env()?.submit(day, part, result)
+}
+
+/// Fetches a private leaderboard and stores its JSON export at `path`.
+pub fn fetch_leaderboard(id: &str, path: &str) -> Result<String, AocClientError> {
+    let json = AocClient::from_env()?.get_leaderboard(id)?;
+    fs::write(path, &json)?;
+    Ok(json)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the contents of the `<main>` element, or the whole document if there is none.
+fn main_content(html: &str) -> &str {
+    let Some(start) = html.find("<main>") else {
+        return html;
+    };
+    let end = html[start..]
+        .find("</main>")
+        .map_or(html.len(), |x| start + x);
+    &html[start + "<main>".len()..end]
+}
+
+/// Decodes the HTML entities that appear in puzzle pages. `&amp;` goes last so `&amp;lt;` stays `&lt;`.
+pub(crate) fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn get_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{name}=\"");
+    let start = tag.find(&pattern)? + pattern.len();
+    let end = tag[start..].find('"')? + start;
+    Some(&tag[start..end])
+}
+
+/// Converts the subset of HTML used by puzzle pages to markdown.
+/// Emphasised code (`<code><em>` or `<em><code>`) is rendered as ``*`x`*``.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    let mut in_pre = false;
+    let mut skip_depth = 0_i32;
+    let mut code: Option<(String, bool)> = None;
+    let mut links: Vec<String> = vec![];
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            if skip_depth == 0 {
+                push_text(&mut out, &mut code, rest);
+            }
+            break;
+        };
+
+        if skip_depth == 0 {
+            push_text(&mut out, &mut code, &rest[..tag_start]);
+        }
+
+        let Some(tag_end) = rest[tag_start..].find('>').map(|x| tag_start + x) else {
+            break;
+        };
+
+        let tag = &rest[tag_start + 1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        if matches!(name, "form" | "script" | "style") {
+            skip_depth += if is_closing { -1 } else { 1 };
+            continue;
+        }
+
+        if skip_depth > 0 {
+            continue;
+        }
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", false) if !in_pre => code = Some((String::new(), false)),
+            ("code", true) if !in_pre => {
+                if let Some((text, emphasised)) = code.take() {
+                    if emphasised {
+                        out.push_str(&format!("*`{text}`*"));
+                    } else {
+                        out.push_str(&format!("`{text}`"));
+                    }
+                }
+            }
+            ("em", _) if in_pre => {}
+            ("em", false) => match &mut code {
+                Some((_, emphasised)) => *emphasised = true,
+                None => out.push('*'),
+            },
+            ("em", true) if code.is_none() => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("ul", true) => out.push('\n'),
+            ("br", _) => out.push('\n'),
+            ("a", false) => {
+                links.push(get_attribute(tag, "href").unwrap_or_default().to_string());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    let mut markdown = out
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+
+    format!("{}\n", markdown.trim())
+}
+
+fn push_text(out: &mut String, code: &mut Option<(String, bool)>, text: &str) {
+    let text = decode_entities(text);
+    match code {
+        Some((buf, _)) => buf.push_str(&text),
+        None => out.push_str(&text),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, html_to_markdown, main_content, AocClient, AocClientError};
+    use crate::day;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// A local stand-in for the Advent of Code server.
+    /// Answers a single request with `status` and `body`, and sends the raw request back to the test.
+    fn serve_once(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (format!("http://{addr}"), rx)
+    }
+
+    #[test]
+    fn fetches_input_with_session_cookie() {
+        let (url, rx) = serve_once(200, "1 2 3\n");
+        let client = AocClient::new(&url, "abc", 2023);
+        assert_eq!(client.get_input(day!(5)).unwrap(), "1 2 3\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/5/input HTTP/1.1"));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (url, _rx) = serve_once(
+            200,
+            "<html><main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Hello <em>world</em>.</p></article></main></html>",
+        );
+        let client = AocClient::new(&url, "abc", 2023);
+        assert_eq!(
+            client.get_puzzle(day!(1)).unwrap(),
+            "## --- Day 1: Trebuchet?! ---\n\nHello *world*.\n"
+        );
+    }
+
+    #[test]
+    fn submits_answer_as_form() {
+        let (url, rx) = serve_once(
+            200,
+            "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "abc", 2023);
+        assert_eq!(
+            client.submit(day!(1), 2, "42").unwrap(),
+            "That's the right answer!  You are one gold star closer."
+        );
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_status_errors() {
+        let (url, _rx) = serve_once(404, "not found");
+        let client = AocClient::new(&url, "abc", 2023);
+        assert!(matches!(
+            client.get_input(day!(1)),
+            Err(AocClientError::Status(404))
+        ));
+    }
+
+    #[test]
+    fn reports_expired_session_for_server_errors() {
+        let (url, _rx) = serve_once(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+        let client = AocClient::new(&url, "abc", 2023);
+        assert!(matches!(
+            client.get_input(day!(1)),
+            Err(AocClientError::SessionExpired)
+        ));
+    }
+
+    #[test]
+    fn reports_expired_session_for_login_page() {
+        let (url, _rx) = serve_once(
+            200,
+            "<html><header><a href=\"/2023/auth/login\">[Log In]</a></header><main>...</main></html>",
+        );
+        let client = AocClient::new(&url, "abc", 2023);
+        assert!(matches!(
+            client.submit(day!(1), 1, "42"),
+            Err(AocClientError::SessionExpired)
+        ));
+    }
+
+    #[test]
+    fn checks_session() {
+        let (url, rx) = serve_once(200, "<html><main>Private Leaderboard</main></html>");
+        let client = AocClient::new(&url, "abc", 2023);
+        assert!(client.check_session().is_ok());
+        assert!(rx
+            .recv()
+            .unwrap()
+            .starts_with("GET /2023/leaderboard/private HTTP/1.1"));
+    }
+
+    #[test]
+    fn extracts_main_content() {
+        assert_eq!(main_content("<body><main>foo</main></body>"), "foo");
+        assert_eq!(main_content("foo"), "foo");
+    }
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html = [
+            "<article class=\"day-desc\"><h2>--- Day 2: Cube Conundrum ---</h2>",
+            "<p>For example:</p>",
+            "<pre><code>Game 1: 3 blue, <em>4 red</em>\n</code></pre>",
+            "<ul><li>One &amp; two</li><li><code>a &lt; b</code></li></ul>",
+            "<p>The answer is <code><em>8</em></code>, see <a href=\"/2023/about\">about</a>.</p>",
+            "<form method=\"post\"><input type=\"hidden\"/></form>",
+            "</article>",
+        ]
+        .join("");
+
+        let expected = [
+            "## --- Day 2: Cube Conundrum ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "Game 1: 3 blue, 4 red",
+            "```",
+            "",
+            "- One & two",
+            "- `a < b`",
+            "",
+            "The answer is *`8`*, see [about](/2023/about).",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(html_to_markdown(&html), expected);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("&quot;it&#x27;s&#39;&nbsp;&lt;&gt;&quot; &amp;lt;"),
+            "\"it's' <>\" &lt;"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+/// Module that renders benchmark timings into a self-contained SVG bar chart.
+/// Bars are drawn on a logarithmic scale and coloured by the bucket their duration falls into.
+use std::{fmt::Write, fs, io, path::Path};
+
+use crate::template::timings::{parse_duration, Timings};
+
+pub static CHART_FILE_PATH: &str = "./.assets/benchmarks.svg";
+
+const WIDTH: f64 = 720.0;
+const LABEL_WIDTH: f64 = 72.0;
+const VALUE_WIDTH: f64 = 80.0;
+const HEADER_HEIGHT: f64 = 40.0;
+const FOOTER_HEIGHT: f64 = 32.0;
+const BAR_HEIGHT: f64 = 10.0;
+const ROW_HEIGHT: f64 = 2.0 * BAR_HEIGHT + 10.0;
+
+const FONT: &str = "font-family=\"Menlo,Consolas,monospace\"";
+
+/// Upper bounds (exclusive, in nanoseconds) and colours of the buckets bars are sorted into.
+const BUCKETS: [(f64, &str, &str); 4] = [
+    (100_000.0, "#2da44e", "&lt; 100µs"),
+    (1_000_000.0, "#9ac23c", "&lt; 1ms"),
+    (10_000_000.0, "#d4a72c", "&lt; 10ms"),
+    (f64::INFINITY, "#cf222e", "≥ 10ms"),
+];
+
+/// A logarithmic scale spanning whole decades of nanoseconds.
+struct Scale {
+    min_exp: i32,
+    max_exp: i32,
+}
+
+impl Scale {
+    fn from_values(values: &[f64]) -> Self {
+        let exps = values.iter().filter(|x| **x > 0.0).map(|x| x.log10());
+
+        #[allow(clippy::cast_possible_truncation)]
+        let min_exp = exps.clone().fold(f64::INFINITY, f64::min).floor() as i32;
+        #[allow(clippy::cast_possible_truncation)]
+        let max_exp = exps.fold(f64::NEG_INFINITY, f64::max).ceil() as i32;
+
+        if values.is_empty() || min_exp > max_exp {
+            return Self {
+                min_exp: 0,
+                max_exp: 1,
+            };
+        }
+
+        Self {
+            min_exp,
+            max_exp: max_exp.max(min_exp + 1),
+        }
+    }
+
+    /// Offset in pixels of the decade `10^exp` from the start of the plot.
+    fn position(&self, exp: f64, plot_width: f64) -> f64 {
+        let span = f64::from(self.max_exp - self.min_exp);
+        (exp - f64::from(self.min_exp)) / span * plot_width
+    }
+
+    /// Width in pixels of a bar representing `nanos`.
+    fn width(&self, nanos: f64, plot_width: f64) -> f64 {
+        self.position(nanos.max(1.0).log10(), plot_width)
+            .clamp(1.0, plot_width)
+    }
+}
+
+fn bucket_color(nanos: f64) -> &'static str {
+    BUCKETS
+        .iter()
+        .find(|(bound, _, _)| nanos < *bound)
+        .map_or(BUCKETS[BUCKETS.len() - 1].1, |(_, color, _)| color)
+}
+
+fn format_decade(exp: i32) -> String {
+    const UNITS: [&str; 4] = ["ns", "µs", "ms", "s"];
+    let exp = exp.max(0).unsigned_abs();
+    let unit = (exp / 3).min(3);
+    format!("{}{}", 10_u64.pow(exp - unit * 3), UNITS[unit as usize])
+}
+
+fn construct_chart(timings: &Timings) -> String {
+    let parts: Vec<[Option<(f64, &str)>; 2]> = timings
+        .data
+        .iter()
+        .map(|timing| {
+            [&timing.part_1, &timing.part_2].map(|part| {
+                part.as_deref()
+                    .and_then(|s| parse_duration(s).map(|nanos| (nanos, s)))
+            })
+        })
+        .collect();
+
+    let values: Vec<f64> = parts.iter().flatten().flatten().map(|x| x.0).collect();
+    let scale = Scale::from_values(&values);
+
+    let plot_width = WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    #[allow(clippy::cast_precision_loss)]
+    let plot_height = ROW_HEIGHT * timings.data.len().max(1) as f64;
+    let height = HEADER_HEIGHT + plot_height + FOOTER_HEIGHT;
+
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" viewBox=\"0 0 {WIDTH} {height}\">"
+    );
+    let _ = writeln!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" fill=\"#ffffff\" />"
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"{LABEL_WIDTH}\" y=\"16\" {FONT} font-size=\"11\" font-weight=\"bold\" fill=\"#24292f\">Benchmarks (log scale, top: part 1, bottom: part 2)</text>"
+    );
+
+    // legend
+    let mut legend_x = LABEL_WIDTH;
+    for (_, color, label) in BUCKETS {
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{legend_x}\" y=\"24\" width=\"10\" height=\"10\" fill=\"{color}\" />"
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"33\" {FONT} font-size=\"11\" fill=\"#57606a\">{label}</text>",
+            legend_x + 14.0
+        );
+        legend_x += 80.0;
+    }
+
+    // decade grid lines
+    let axis_y = HEADER_HEIGHT + plot_height;
+    for exp in scale.min_exp..=scale.max_exp {
+        let x = LABEL_WIDTH + scale.position(f64::from(exp), plot_width);
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{x:.1}\" y1=\"{HEADER_HEIGHT}\" x2=\"{x:.1}\" y2=\"{axis_y}\" stroke=\"#d0d7de\" stroke-width=\"1\" />"
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{x:.1}\" y=\"{}\" {FONT} font-size=\"11\" fill=\"#57606a\" text-anchor=\"middle\">{}</text>",
+            axis_y + 16.0,
+            format_decade(exp)
+        );
+    }
+
+    for (i, (timing, parts)) in timings.data.iter().zip(&parts).enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let row_y = HEADER_HEIGHT + ROW_HEIGHT * i as f64 + 5.0;
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\" {FONT} font-size=\"11\" fill=\"#24292f\">Day {}</text>",
+            row_y + BAR_HEIGHT + 4.0,
+            timing.day.into_inner()
+        );
+
+        for (part_index, part) in parts.iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let bar_y = row_y + BAR_HEIGHT * part_index as f64;
+
+            match part {
+                Some((nanos, label)) => {
+                    let bar_width = scale.width(*nanos, plot_width);
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{LABEL_WIDTH}\" y=\"{bar_y}\" width=\"{bar_width:.1}\" height=\"{}\" fill=\"{}\"><title>Day {} part {}: {label}</title></rect>",
+                        BAR_HEIGHT - 1.0,
+                        bucket_color(*nanos),
+                        timing.day.into_inner(),
+                        part_index + 1
+                    );
+                    let _ = writeln!(
+                        svg,
+                        "<text x=\"{:.1}\" y=\"{}\" {FONT} font-size=\"9\" fill=\"#57606a\">{label}</text>",
+                        LABEL_WIDTH + bar_width + 4.0,
+                        bar_y + BAR_HEIGHT - 2.0
+                    );
+                }
+                None => {
+                    let _ = writeln!(
+                        svg,
+                        "<text x=\"{}\" y=\"{}\" {FONT} font-size=\"9\" fill=\"#57606a\">-</text>",
+                        LABEL_WIDTH + 4.0,
+                        bar_y + BAR_HEIGHT - 2.0
+                    );
+                }
+            }
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render `timings` to [`CHART_FILE_PATH`].
+pub fn store(timings: &Timings) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(CHART_FILE_PATH).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(CHART_FILE_PATH, construct_chart(timings))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bucket_color, construct_chart, format_decade, Scale, BUCKETS};
+    use crate::{day, template::timings::Timing, template::timings::Timings};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("26.7µs".into()),
+                    part_2: Some("115.8µs".into()),
+                    total_nanos: 142_500.0,
+                    build: None,
+                },
+                Timing {
+                    day: day!(8),
+                    part_1: Some("347.0µs".into()),
+                    part_2: Some("154.5ms".into()),
+                    total_nanos: 154_847_000.0,
+                    build: None,
+                },
+                Timing {
+                    day: day!(14),
+                    part_1: Some("25.4µs".into()),
+                    part_2: None,
+                    total_nanos: 25_400.0,
+                    build: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(2), "100ns");
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(6), "1ms");
+        assert_eq!(format_decade(9), "1s");
+        assert_eq!(format_decade(10), "10s");
+    }
+
+    #[test]
+    fn scale_spans_whole_decades() {
+        let scale = Scale::from_values(&[25_400.0, 154_500_000.0]);
+        assert_eq!(scale.min_exp, 4);
+        assert_eq!(scale.max_exp, 9);
+    }
+
+    #[test]
+    fn scale_handles_empty_values() {
+        let scale = Scale::from_values(&[]);
+        assert_eq!(scale.min_exp, 0);
+        assert_eq!(scale.max_exp, 1);
+    }
+
+    #[test]
+    fn colors_by_bucket() {
+        assert_eq!(bucket_color(500.0), BUCKETS[0].1);
+        assert_eq!(bucket_color(500_000.0), BUCKETS[1].1);
+        assert_eq!(bucket_color(5_000_000.0), BUCKETS[2].1);
+        assert_eq!(bucket_color(154_500_000.0), BUCKETS[3].1);
+    }
+
+    #[test]
+    fn renders_bar_per_part() {
+        let svg = construct_chart(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<title>").count(), 5);
+        assert!(svg.contains("Day 8 part 2: 154.5ms"));
+        assert!(svg.contains(">Day 14</text>"));
+    }
+}
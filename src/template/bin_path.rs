@@ -0,0 +1,50 @@
+/// Module that locates the source of a day's solution.
+/// A solution is either a single file `src/bin/NN.rs` or a directory `src/bin/NN/` with a `main.rs` and submodules.
+/// Cargo picks up both layouts as the binary `NN`.
+use std::path::Path;
+
+use crate::template::Day;
+
+/// Path of a solution that lives in a single file.
+#[must_use]
+pub fn get_file_path(day: Day) -> String {
+    format!("./src/bin/{day}.rs")
+}
+
+/// Path of the entry point of a solution that lives in a directory.
+#[must_use]
+pub fn get_dir_path(day: Day) -> String {
+    format!("./src/bin/{day}/main.rs")
+}
+
+/// Path of the entry point of `day`'s solution, or `None` if it has not been scaffolded yet.
+#[must_use]
+pub fn find_path_for_bin(day: Day) -> Option<String> {
+    [get_file_path(day), get_dir_path(day)]
+        .into_iter()
+        .find(|path| Path::new(path).exists())
+}
+
+/// Path of the entry point of `day`'s solution, defaulting to the single file layout.
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    find_path_for_bin(day).unwrap_or_else(|| get_file_path(day))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_path_for_bin, get_dir_path, get_path_for_bin};
+    use crate::day;
+
+    #[test]
+    fn finds_single_file_solutions() {
+        assert_eq!(find_path_for_bin(day!(1)), Some("./src/bin/01.rs".into()));
+    }
+
+    #[test]
+    fn defaults_to_single_file_layout() {
+        assert_eq!(find_path_for_bin(day!(25)), None);
+        assert_eq!(get_path_for_bin(day!(25)), "./src/bin/25.rs");
+        assert_eq!(get_dir_path(day!(25)), "./src/bin/25/main.rs");
+    }
+}
@@ -0,0 +1,173 @@
+/// Module that handles build options which are passed through to `cargo` when running solutions.
+use std::{env, process::Command};
+
+/// Target directory for `--native` builds, so they don't invalidate the cache of portable builds.
+static NATIVE_TARGET_DIR: &str = "target/native";
+
+/// Extra options for the `cargo run` invocations of `solve`, `all` and `time`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildOptions {
+    /// Passed as `--features`.
+    pub features: Option<String>,
+    /// Passed as `--profile`, replaces `--release`.
+    pub profile: Option<String>,
+    /// Passed as `--target-dir`.
+    pub target_dir: Option<String>,
+    /// Builds with `RUSTFLAGS=-C target-cpu=native`.
+    pub native: bool,
+}
+
+impl BuildOptions {
+    /// Arguments for `cargo run`. Without a `--profile`, `release` adds `--release`.
+    pub fn cargo_args(&self, release: bool) -> Vec<String> {
+        let mut args = vec![];
+
+        match &self.profile {
+            Some(profile) => args.extend(["--profile".into(), profile.clone()]),
+            None if release => args.push("--release".into()),
+            None => {}
+        }
+
+        if let Some(features) = &self.features {
+            args.extend(["--features".into(), features.clone()]);
+        }
+
+        match &self.target_dir {
+            Some(target_dir) => args.extend(["--target-dir".into(), target_dir.clone()]),
+            None if self.native => args.extend(["--target-dir".into(), NATIVE_TARGET_DIR.into()]),
+            None => {}
+        }
+
+        args
+    }
+
+    /// Value of `RUSTFLAGS` for the build, extending the flags that are set already.
+    fn rustflags(&self, current: Option<String>) -> Option<String> {
+        if !self.native {
+            return current;
+        }
+
+        let native = "-C target-cpu=native";
+        Some(match current {
+            Some(flags) if !flags.trim().is_empty() => format!("{flags} {native}"),
+            _ => native.into(),
+        })
+    }
+
+    /// Options for a DHAT run: the `dhat` profile (unless another profile is set) and the `dhat-heap` feature.
+    #[must_use]
+    pub fn with_dhat(&self) -> Self {
+        let features = match &self.features {
+            Some(features) => format!("dhat-heap,{features}"),
+            None => "dhat-heap".into(),
+        };
+
+        BuildOptions {
+            features: Some(features),
+            profile: self.profile.clone().or_else(|| Some("dhat".into())),
+            ..self.clone()
+        }
+    }
+
+    /// Sets the environment of `cmd` for the build.
+    pub fn apply_env(&self, cmd: &mut Command) {
+        if let Some(flags) = self.rustflags(env::var("RUSTFLAGS").ok()) {
+            cmd.env("RUSTFLAGS", flags);
+        }
+    }
+
+    /// Describes the options that influence timings, e.g. `native, profile=bench`.
+    /// Returns `None` for a default build.
+    pub fn label(&self) -> Option<String> {
+        let mut parts = vec![];
+
+        if self.native {
+            parts.push("native".to_string());
+        }
+        if let Some(profile) = &self.profile {
+            parts.push(format!("profile={profile}"));
+        }
+        if let Some(features) = &self.features {
+            parts.push(format!("features={features}"));
+        }
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(", "))
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BuildOptions;
+
+    #[test]
+    fn defaults_to_plain_build() {
+        let options = BuildOptions::default();
+        assert_eq!(options.cargo_args(false), Vec::<String>::new());
+        assert_eq!(options.cargo_args(true), vec!["--release"]);
+        assert_eq!(options.label(), None);
+        assert_eq!(options.rustflags(None), None);
+    }
+
+    #[test]
+    fn passes_options_to_cargo() {
+        let options = BuildOptions {
+            features: Some("dhat-heap".into()),
+            profile: Some("bench".into()),
+            target_dir: Some("/tmp/aoc".into()),
+            native: false,
+        };
+
+        assert_eq!(
+            options.cargo_args(true),
+            vec![
+                "--profile",
+                "bench",
+                "--features",
+                "dhat-heap",
+                "--target-dir",
+                "/tmp/aoc"
+            ]
+        );
+        assert_eq!(
+            options.label(),
+            Some("profile=bench, features=dhat-heap".into())
+        );
+    }
+
+    #[test]
+    fn adds_dhat_options() {
+        let options = BuildOptions {
+            features: Some("foo".into()),
+            ..Default::default()
+        }
+        .with_dhat();
+
+        assert_eq!(
+            options.cargo_args(true),
+            vec!["--profile", "dhat", "--features", "dhat-heap,foo"]
+        );
+    }
+
+    #[test]
+    fn builds_native_in_separate_target_dir() {
+        let options = BuildOptions {
+            native: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options.cargo_args(true),
+            vec!["--release", "--target-dir", "target/native"]
+        );
+        assert_eq!(options.label(), Some("native".into()));
+        assert_eq!(
+            options.rustflags(Some("-D warnings".into())),
+            Some("-D warnings -C target-cpu=native".into())
+        );
+        assert_eq!(options.rustflags(None), Some("-C target-cpu=native".into()));
+    }
+}
@@ -0,0 +1,175 @@
+/// Module that records SHA-256 checksums of downloaded inputs in `data/checksums.json`,
+/// so that accidental edits to `data/inputs/` (e.g. a CRLF conversion) are noticed.
+use std::{collections::HashMap, fmt::Write, fs, io, str::FromStr};
+
+use sha2::{Digest, Sha256};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+static CHECKSUMS_FILE_PATH: &str = "./data/checksums.json";
+
+/// Returns the hex-encoded SHA-256 digest of `content`.
+pub fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .fold(String::new(), |mut s, byte| {
+            let _ = write!(s, "{byte:02x}");
+            s
+        })
+}
+
+/// Recorded input checksums, keyed by day.
+#[derive(Clone, Debug, Default)]
+pub struct Checksums {
+    pub data: HashMap<Day, String>,
+}
+
+impl Checksums {
+    /// Dehydrate checksums to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(CHECKSUMS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate checksums from a JSON file. If not present, returns empty checksums.
+    pub fn read_from_file() -> Self {
+        let s = match fs::read_to_string(CHECKSUMS_FILE_PATH) {
+            Ok(s) => Checksums::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Checksums::default(),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match s {
+            Ok(checksums) => checksums,
+            Err(e) => {
+                eprintln!("{e}");
+                Checksums::default()
+            }
+        }
+    }
+
+    /// Records the checksum of `input` for `day`.
+    pub fn record(&mut self, day: Day, input: &str) {
+        self.data.insert(day, sha256_hex(input.as_bytes()));
+    }
+
+    /// Returns `false` if a checksum is recorded for `day` and `input` does not match it.
+    pub fn matches(&self, day: Day, input: &str) -> bool {
+        match self.data.get(&day) {
+            Some(checksum) => *checksum == sha256_hex(input.as_bytes()),
+            None => true,
+        }
+    }
+}
+
+/// Records the checksum of a freshly downloaded input.
+pub fn record_input(day: Day, input: &str) -> Result<(), io::Error> {
+    let mut checksums = Checksums::read_from_file();
+    checksums.record(day, input);
+    checksums.store_file()
+}
+
+/// Prints a warning if `input` no longer matches the checksum recorded when it was downloaded.
+pub fn verify_input(day: Day, input: &str) {
+    if Checksums::read_from_file().matches(day, input) {
+        return;
+    }
+
+    eprintln!(
+        "{ANSI_BOLD}⚠️  WARNING: data/inputs/{day}.txt does not match the checksum recorded when it was downloaded.{ANSI_RESET}"
+    );
+
+    if input.contains("\r\n") {
+        eprintln!("{ANSI_BOLD}⚠️  The file contains CRLF line endings, it was probably converted by git or an editor.{ANSI_RESET}");
+    }
+
+    eprintln!("{ANSI_BOLD}⚠️  Run `cargo download {day} --force` to restore it.{ANSI_RESET}");
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Checksums> for JsonValue {
+    fn from(value: Checksums) -> Self {
+        let map: HashMap<String, JsonValue> = value
+            .data
+            .into_iter()
+            .map(|(day, checksum)| (day.to_string(), JsonValue::String(checksum)))
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Checksums {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(Checksums {
+            data: json_data
+                .iter()
+                .map(|(day, checksum)| {
+                    let day =
+                        Day::from_str(day).map_err(|_| format!("Expected `{day}` to be a day."))?;
+                    let checksum = checksum
+                        .get::<String>()
+                        .ok_or("Expected checksum to be a string.")?;
+                    Ok((day, checksum.clone()))
+                })
+                .collect::<Result<_, String>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{sha256_hex, Checksums};
+    use crate::day;
+
+    #[test]
+    fn hashes_content() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn detects_modified_inputs() {
+        let mut checksums = Checksums::default();
+        checksums.record(day!(1), "1\n2\n");
+
+        assert!(checksums.matches(day!(1), "1\n2\n"));
+        assert!(!checksums.matches(day!(1), "1\r\n2\r\n"));
+        assert!(checksums.matches(day!(2), "anything"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut checksums = Checksums::default();
+        checksums.record(day!(7), "abc");
+
+        let json = tinyjson::JsonValue::from(checksums).stringify().unwrap();
+        let parsed = Checksums::try_from(json).unwrap();
+        assert_eq!(
+            parsed.data.get(&day!(7)).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_checksums() {
+        let json = r#"{ "26": "abc" }"#.to_string();
+        Checksums::try_from(json).unwrap();
+    }
+}
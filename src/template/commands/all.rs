@@ -1,5 +1,49 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::fs;
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    run_multi(&all_days().collect(), is_release, is_timed);
+use crate::template::{
+    all_days,
+    build_options::BuildOptions,
+    dhat_report::{self, HeapSummary},
+    run_multi::run_multi,
+};
+
+pub fn handle(is_release: bool, is_timed: bool, dhat: bool, options: &BuildOptions) {
+    if dhat {
+        handle_dhat(options);
+    } else {
+        run_multi(&all_days().collect(), is_release, is_timed, options);
+    }
+}
+
+/// Profiles all solutions with DHAT and prints a table of their heap usage.
+/// Timings are skipped, as the profiling allocator distorts them.
+fn handle_dhat(options: &BuildOptions) {
+    // remove reports of earlier runs, so unsolved days don't show up with stale numbers.
+    for day in all_days() {
+        for part in [1, 2] {
+            let _ = fs::remove_file(dhat_report::get_report_path(day, part));
+        }
+    }
+
+    run_multi(&all_days().collect(), false, false, &options.with_dhat());
+
+    let mut summaries = vec![];
+    for day in all_days() {
+        for part in [1, 2] {
+            match HeapSummary::read(day, part) {
+                Some(Ok(summary)) => summaries.push((day, part, summary)),
+                Some(Err(e)) => {
+                    eprintln!("Failed to read DHAT report of day {day}, part {part}: {e}")
+                }
+                None => {}
+            }
+        }
+    }
+
+    println!();
+    if summaries.is_empty() {
+        println!("No DHAT reports were written.");
+    } else {
+        println!("{}", dhat_report::table(&summaries));
+    }
 }
@@ -1,14 +1,56 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{
+    aoc_cli::{self, get_input_path, get_puzzle_path, has_content},
+    aoc_client, checksums, Day,
+};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Downloads input and puzzle for `day`. Exits if the download fails.
+pub fn handle(day: Day, force: bool) {
+    if let Err(e) = download(day, force) {
+        eprintln!("{e}");
         process::exit(1);
     }
+}
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+/// Downloads input and puzzle for `day`. Files that already have content are skipped unless `force` is set.
+pub fn download(day: Day, force: bool) -> Result<(), String> {
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    let input = force || !has_content(&input_path);
+    let puzzle = force || !has_content(&puzzle_path);
+
+    if !input {
+        println!("Skipped input, \"{input_path}\" already exists. Use `--force` to overwrite it.");
+    }
+    if !puzzle {
+        println!(
+            "Skipped puzzle, \"{puzzle_path}\" already exists. Use `--force` to overwrite it."
+        );
+    }
+    if !input && !puzzle {
+        return Ok(());
+    }
+
+    if aoc_client::use_aoc_cli() {
+        if aoc_cli::check().is_err() {
+            return Err("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.".into());
+        }
+
+        aoc_cli::download(day, input, puzzle)
+            .map_err(|e| format!("failed to call aoc-cli: {e}"))?;
+    } else {
+        aoc_client::download(day, input, puzzle)
+            .map_err(|e| format!("failed to download puzzle: {e}"))?;
+    }
+
+    if input {
+        let recorded = fs::read_to_string(&input_path)
+            .and_then(|content| checksums::record_input(day, &content));
+        if let Err(e) = recorded {
+            eprintln!("failed to record input checksum: {e}");
+        }
+    }
+
+    Ok(())
 }
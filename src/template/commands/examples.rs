@@ -0,0 +1,239 @@
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    process,
+};
+
+use crate::template::{aoc_cli::has_content, bin_path::get_path_for_bin, puzzle, Day};
+
+fn get_example_path(day: Day, part: u8) -> String {
+    match part {
+        1 => format!("data/examples/{day}.txt"),
+        _ => format!("data/examples/{day}-{part}.txt"),
+    }
+}
+
+/// Lets the user pick one of `options`. Falls back to `default` when stdin is not interactive.
+/// Returns `None` if the user chose to skip.
+fn choose(label: &str, options: &[String], default: usize) -> Option<usize> {
+    if options.len() == 1 {
+        return Some(0);
+    }
+
+    println!("Found {} candidates for the {label}:", options.len());
+    for (i, option) in options.iter().enumerate() {
+        let preview = if option.contains('\n') {
+            format!("\n{}", option.trim_end())
+        } else {
+            format!(" {option}")
+        };
+        println!("  [{}]{preview}", i + 1);
+    }
+
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        println!("Using [{}].", default + 1);
+        return Some(default);
+    }
+
+    loop {
+        print!("Choose one, or `s` to skip [{}]: ", default + 1);
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).is_err() {
+            return Some(default);
+        }
+
+        match line.trim() {
+            "" => return Some(default),
+            "s" => return None,
+            x => match x.parse::<usize>() {
+                Ok(n) if (1..=options.len()).contains(&n) => return Some(n - 1),
+                _ => println!("Please enter a number between 1 and {}.", options.len()),
+            },
+        }
+    }
+}
+
+fn write_example(day: Day, part: u8, content: &str, force: bool) {
+    let path = get_example_path(day, part);
+
+    if !force && has_content(&path) {
+        println!("Skipped example, \"{path}\" already exists. Use `--force` to overwrite it.");
+        return;
+    }
+
+    match fs::write(&path, content) {
+        Ok(()) => println!("Created example file \"{path}\""),
+        Err(e) => eprintln!("Failed to write example file \"{path}\": {e}"),
+    }
+}
+
+/// Returns the body of the test function `name`, i.e. the range up to the next function or the end of the module.
+fn find_test_fn(source: &str, name: &str) -> Option<(usize, usize)> {
+    let start = source.find(&format!("fn {name}()"))?;
+    let end = source[start + 1..]
+        .find("fn ")
+        .map_or(source.len(), |x| start + 1 + x);
+    Some((start, end))
+}
+
+/// Replaces the placeholder assertion in a test function with the expected `answer`.
+/// Tests that already assert a value are left untouched.
+fn patch_test(source: &str, name: &str, answer: &str, example_part: Option<u8>) -> Option<String> {
+    let (start, end) = find_test_fn(source, name)?;
+    let mut body = source[start..end].to_string();
+
+    let placeholder = "assert_eq!(result, None);";
+    if !body.contains(placeholder) {
+        return None;
+    }
+
+    let expected = if answer.parse::<i64>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}.to_string()")
+    };
+    body = body.replacen(
+        placeholder,
+        &format!("assert_eq!(result, Some({expected}));"),
+        1,
+    );
+
+    if let Some(part) = example_part {
+        body = body.replacen(
+            "read_file(\"examples\", DAY)",
+            &format!("read_file_part(\"examples\", DAY, {part})"),
+            1,
+        );
+    }
+
+    Some(format!("{}{body}{}", &source[..start], &source[end..]))
+}
+
+/// Extracts example inputs and expected answers from the downloaded puzzle description for `day`.
+pub fn handle(day: Day, force: bool) {
+    let Some(markdown) = puzzle::read_puzzle(day) else {
+        eprintln!("No puzzle description found for day {day}. Run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let module_path = get_path_for_bin(day);
+    let mut module = fs::read_to_string(&module_path).ok();
+    if module.is_none() {
+        println!("No solution module found at \"{module_path}\", only writing example files.");
+    }
+
+    let (part_one, part_two) = puzzle::split_parts(&markdown);
+    let mut first_example: Option<String> = None;
+
+    for (part, section) in [(1u8, Some(part_one)), (2, part_two)] {
+        let Some(section) = section else {
+            continue;
+        };
+
+        let blocks = puzzle::code_blocks(section);
+        // the first code block of a part is usually the example input, later blocks tend to show intermediate states.
+        let example = choose(&format!("part {part} example"), &blocks, 0).map(|i| &blocks[i]);
+
+        let mut example_part = None;
+        if let Some(example) = example {
+            if first_example.as_ref() != Some(example) {
+                write_example(day, part, example, force);
+                if part > 1 && has_content(&get_example_path(day, part)) {
+                    example_part = Some(part);
+                }
+                first_example.get_or_insert_with(|| example.clone());
+            }
+        } else if part == 1 {
+            println!("No example input found for part {part}.");
+        }
+
+        let answers = puzzle::example_answers(section);
+        // the final result is usually the last emphasised value of the example.
+        let answer = if answers.is_empty() {
+            println!("No example answer found for part {part}.");
+            None
+        } else {
+            choose(&format!("part {part} answer"), &answers, answers.len() - 1).map(|i| &answers[i])
+        };
+
+        let (Some(answer), Some(source)) = (answer, &module) else {
+            continue;
+        };
+
+        let name = if part == 1 {
+            "test_part_one"
+        } else {
+            "test_part_two"
+        };
+        match patch_test(source, name, answer, example_part) {
+            Some(patched) => {
+                println!("Expecting `{answer}` in `{name}`.");
+                module = Some(patched);
+            }
+            None => println!("Skipped `{name}`, it already asserts a result."),
+        }
+    }
+
+    if let Some(module) = module {
+        if let Err(e) = fs::write(&module_path, module) {
+            eprintln!("Failed to update \"{module_path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::patch_test;
+
+    const MODULE: &str = "mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+}
+";
+
+    #[test]
+    fn patches_numeric_answer() {
+        let patched = patch_test(MODULE, "test_part_one", "142", None).unwrap();
+        assert!(patched.contains("assert_eq!(result, Some(142));"));
+        assert_eq!(patched.matches("assert_eq!(result, None);").count(), 1);
+    }
+
+    #[test]
+    fn patches_second_example() {
+        let patched = patch_test(MODULE, "test_part_two", "281", Some(2)).unwrap();
+        assert!(patched.contains(
+            "part_two(&advent_of_code::template::read_file_part(\"examples\", DAY, 2));\n        assert_eq!(result, Some(281));"
+        ));
+        assert!(patched.contains(
+            "part_one(&advent_of_code::template::read_file(\"examples\", DAY));\n        assert_eq!(result, None);"
+        ));
+    }
+
+    #[test]
+    fn patches_string_answer() {
+        let patched = patch_test(MODULE, "test_part_one", "abc", None).unwrap();
+        assert!(patched.contains("assert_eq!(result, Some(\"abc\".to_string()));"));
+    }
+
+    #[test]
+    fn keeps_existing_assertions() {
+        let patched = patch_test(MODULE, "test_part_one", "142", None).unwrap();
+        assert!(patch_test(&patched, "test_part_one", "7", None).is_none());
+        assert!(patch_test(MODULE, "test_part_three", "7", None).is_none());
+    }
+}
@@ -0,0 +1,38 @@
+use std::{fs, process};
+
+use crate::template::{
+    aoc_client,
+    leaderboard::{self, Leaderboard, LEADERBOARD_FILE_PATH},
+};
+
+/// Renders a private leaderboard from its JSON export at `path` (default: `data/leaderboard.json`).
+/// With `fetch`, the export of the leaderboard with that id is downloaded to `path` first.
+/// With `markdown`, the tables are additionally written to that file as markdown.
+pub fn handle(path: Option<String>, fetch: Option<String>, markdown: Option<String>) {
+    let path = path.unwrap_or_else(|| LEADERBOARD_FILE_PATH.into());
+
+    let json = match fetch {
+        Some(id) => aoc_client::fetch_leaderboard(&id, &path).map_err(|e| e.to_string()),
+        None => fs::read_to_string(&path).map_err(|e| format!("failed to read \"{path}\": {e}")),
+    };
+
+    let leaderboard = match json.and_then(Leaderboard::try_from) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("failed to load leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    print!("{}", leaderboard::render(&leaderboard, false));
+
+    if let Some(markdown_path) = markdown {
+        match fs::write(&markdown_path, leaderboard::render(&leaderboard, true)) {
+            Ok(()) => println!("\nExported leaderboard to \"{markdown_path}\"."),
+            Err(e) => {
+                eprintln!("failed to export leaderboard: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 pub mod all;
 pub mod download;
+pub mod examples;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
+pub mod session;
 pub mod solve;
 pub mod time;
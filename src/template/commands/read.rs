@@ -1,8 +1,45 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, aoc_client, puzzle, puzzle_renderer, Day};
+
+/// Prints the puzzle description for `day`, optionally limited to one `part`.
+/// With `offline`, the description stored by `download` is rendered without any network access.
+/// With `new`, only the text added since the previous download is shown.
+pub fn handle(day: Day, offline: bool, part: Option<u8>, new: bool) {
+    if new {
+        print_new_section(day);
+        return;
+    }
+
+    if offline {
+        match puzzle::read_puzzle(day) {
+            Some(markdown) => print_puzzle(day, &markdown, part),
+            None => {
+                eprintln!(
+                    "No puzzle description found for day {day}. Run `cargo download {day}` first."
+                );
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if !aoc_client::use_aoc_cli() {
+        match aoc_client::read(day) {
+            Ok(markdown) => print_puzzle(day, &markdown, part),
+            Err(e) => {
+                eprintln!("failed to read puzzle: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if part.is_some() {
+        eprintln!("`--part` is not supported by aoc-cli, use `--offline` to read a single part.");
+        process::exit(1);
+    }
 
-pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -13,3 +50,58 @@ pub fn handle(day: Day) {
         process::exit(1);
     };
 }
+
+fn print_puzzle(day: Day, markdown: &str, part: Option<u8>) {
+    let (part_one, part_two) = puzzle::split_parts(markdown);
+
+    let section = match part {
+        None => markdown,
+        Some(1) => part_one,
+        Some(2) => match part_two {
+            Some(section) => section,
+            None => {
+                eprintln!("Part two of day {day} is not unlocked yet.");
+                process::exit(1);
+            }
+        },
+        Some(x) => {
+            eprintln!("Unknown part {x}, expected 1 or 2.");
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "{}",
+        puzzle_renderer::render(section, puzzle_renderer::terminal_width())
+    );
+}
+
+/// Prints the text that was added to the stored description when it was last re-downloaded.
+fn print_new_section(day: Day) {
+    let Some(current) = puzzle::read_puzzle(day) else {
+        eprintln!("No puzzle description found for day {day}. Run `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let added = match puzzle::read_previous_puzzle(day) {
+        Some(previous) => puzzle::added_blocks(&previous, &current),
+        // descriptions downloaded before the previous version was kept: fall back to part two.
+        None => match puzzle::split_parts(&current).1 {
+            Some(section) => section.to_string(),
+            None => {
+                eprintln!("No earlier version of day {day} found, the description has not changed since it was downloaded.");
+                process::exit(1);
+            }
+        },
+    };
+
+    if added.is_empty() {
+        println!("Nothing changed since the previous version of day {day}.");
+        return;
+    }
+
+    println!(
+        "{}",
+        puzzle_renderer::render(&added, puzzle_renderer::terminal_width())
+    );
+}
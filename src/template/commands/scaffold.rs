@@ -1,69 +1,288 @@
 use std::{
-    fs::{File, OpenOptions},
+    collections::BTreeSet,
+    env,
+    fs::{self, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    bin_path,
+    placeholders::{self, Context},
+    vscode_launch, Day,
+};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+static TEMPLATES_DIR: &str = "templates";
+const DEFAULT_TEMPLATE: &str = "default";
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+macro_rules! builtin_template {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Templates shipped with the binary. A file with the same name in `templates/` takes precedence.
+const BUILTIN_TEMPLATES: [(&str, &str); 5] = [
+    builtin_template!("default"),
+    builtin_template!("u64"),
+    builtin_template!("grid"),
+    builtin_template!("graph"),
+    builtin_template!("shared-parse"),
+];
+
+/// Names of all built-in and user-defined templates.
+fn available_templates() -> BTreeSet<String> {
+    let mut names: BTreeSet<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(TEMPLATES_DIR) {
+        names.extend(entries.filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_suffix(".txt").map(str::to_string)
+        }));
+    }
+
+    names
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Reads the template `name` from `templates/{name}.txt`, falling back to the built-in template of that name.
+fn read_template(name: &str) -> Result<String, String> {
+    if let Ok(template) = fs::read_to_string(format!("{TEMPLATES_DIR}/{name}.txt")) {
+        return Ok(template);
+    }
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| {
+            let available: Vec<String> = available_templates().into_iter().collect();
+            format!(
+                "unknown template `{name}`. Available templates: {}.",
+                available.join(", ")
+            )
+        })
+}
+
+/// The template to use when none is passed explicitly, configurable with `AOC_TEMPLATE`.
+fn default_template() -> String {
+    env::var("AOC_TEMPLATE").unwrap_or_else(|_| DEFAULT_TEMPLATE.into())
+}
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+/// What happened to a file that scaffold is responsible for.
+#[derive(Debug, PartialEq, Eq)]
+enum FileStatus {
+    Created,
+    Overwritten,
+    Kept,
+}
+
+/// Writes `content` to `path` if the file does not exist yet, or if `force` is set.
+fn write_file(path: &str, content: &str, force: bool) -> Result<FileStatus, std::io::Error> {
+    let exists = Path::new(path).exists();
+    if exists && !force {
+        return Ok(FileStatus::Kept);
+    }
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    file.write_all(content.as_bytes())?;
+
+    Ok(if exists {
+        FileStatus::Overwritten
+    } else {
+        FileStatus::Created
+    })
+}
+
+/// Creates the `kind` file for one piece of a day and reports what happened. Exits if the file can't be written.
+fn scaffold_file(kind: &str, path: &str, content: &str, force: bool, note: &str) -> FileStatus {
+    match write_file(path, content, force) {
+        Ok(status) => {
+            match status {
+                FileStatus::Created if content.is_empty() => {
+                    println!("Created empty {kind} \"{path}\"{note}")
+                }
+                FileStatus::Created => println!("Created {kind} \"{path}\"{note}"),
+                FileStatus::Overwritten => println!("Overwrote {kind} \"{path}\"{note}"),
+                FileStatus::Kept => println!("Kept existing {kind} \"{path}\""),
+            }
+            status
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to create {kind} \"{path}\": {e}");
             process::exit(1);
         }
     }
+}
+
+/// Creates the empty input and example files. They are only created if missing and never overwritten, not even with
+/// `--force`, as they hold downloaded or pasted data.
+fn scaffold_data_files(input_path: &str, example_path: &str) -> [FileStatus; 2] {
+    [
+        scaffold_file("input file", input_path, "", false, ""),
+        scaffold_file("example file", example_path, "", false, ""),
+    ]
+}
+
+/// Picks the module path for `day`: an existing solution in either layout, or a new one in the layout chosen with `dir`.
+fn get_module_path(day: Day, dir: bool) -> Result<String, String> {
+    match bin_path::find_path_for_bin(day) {
+        Some(path) if dir && path != bin_path::get_dir_path(day) => Err(format!(
+            "day {day} already exists as \"{path}\". Move it to \"{}\" to split it into modules.",
+            bin_path::get_dir_path(day)
+        )),
+        Some(path) => Ok(path),
+        None if dir => Ok(bin_path::get_dir_path(day)),
+        None => Ok(bin_path::get_file_path(day)),
+    }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+/// Creates the module, input and example files for `day`. Files that exist already are kept, so running scaffold
+/// again repairs a partially set up day. `force` overwrites the module file, inputs and examples are always kept.
+/// With `dir`, the module is created as `src/bin/NN/main.rs` so the solution can be split into submodules.
+/// Unless `no_editor` is set, debug configurations for the day are added to `.vscode/launch.json`.
+pub fn handle(day: Day, template: Option<String>, dir: bool, no_editor: bool, force: bool) {
+    let template_name = template.unwrap_or_else(default_template);
+    let module_template = match read_template(&template_name) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to read template: {e}");
+            process::exit(1);
         }
+    };
+
+    let input_path = format!("data/inputs/{day}.txt");
+    let example_path = format!("data/examples/{day}.txt");
+    let module_path = match get_module_path(day, dir) {
+        Ok(path) => path,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to scaffold module: {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    if let Some(parent) = Path::new(&module_path).parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Failed to create directory \"{}\": {e}", parent.display());
+            process::exit(1);
         }
+    }
+
+    let module = match placeholders::render(&module_template, &Context::for_day(day)) {
+        Ok(module) => module,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to render template `{template_name}`: {e}");
             process::exit(1);
         }
+    };
+
+    let module_status = scaffold_file(
+        "module file",
+        &module_path,
+        &module,
+        force,
+        &format!(" from template `{template_name}`"),
+    );
+    scaffold_data_files(&input_path, &example_path);
+
+    if module_status == FileStatus::Kept {
+        println!("Use `--force` to overwrite the existing module file.");
+    }
+
+    if !no_editor {
+        match vscode_launch::add_day(day) {
+            Ok(true) => {
+                println!("Added debug configurations for day {day} to \".vscode/launch.json\"")
+            }
+            Ok(false) => {}
+            Err(e) => eprintln!("Failed to add debug configurations: {e}"),
+        }
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        available_templates, read_template, scaffold_data_files, write_file, FileStatus,
+        BUILTIN_TEMPLATES,
+    };
+    use crate::template::placeholders::{self, Context};
+
+    #[test]
+    fn builtin_templates_are_solution_modules() {
+        for (name, template) in BUILTIN_TEMPLATES {
+            let module = placeholders::render(template, &Context::default()).unwrap();
+            assert!(
+                module.contains("\nadvent_of_code::solution!(%DAY_NUMBER%);\n"),
+                "{name}"
+            );
+            assert!(template.contains("fn test_part_one()"), "{name}");
+            assert!(template.contains("fn test_part_two()"), "{name}");
+        }
+    }
+
+    #[test]
+    fn reads_templates_by_name() {
+        assert!(read_template("grid").unwrap().contains("fn neighbours"));
+        assert!(available_templates().contains("shared-parse"));
+
+        let error = read_template("does-not-exist").unwrap_err();
+        assert!(error.contains("Available templates: default, graph, grid, shared-parse, u64"));
+    }
+
+    #[test]
+    fn keeps_existing_files_unless_forced() {
+        let path = std::env::temp_dir().join(format!("aoc-scaffold-{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(write_file(path, "a", false).unwrap(), FileStatus::Created);
+        assert_eq!(write_file(path, "b", false).unwrap(), FileStatus::Kept);
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "a");
+        assert_eq!(
+            write_file(path, "c", true).unwrap(),
+            FileStatus::Overwritten
+        );
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "c");
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn never_overwrites_inputs_and_examples() {
+        let dir = std::env::temp_dir();
+        let input = dir.join(format!("aoc-scaffold-input-{}.txt", std::process::id()));
+        let example = dir.join(format!("aoc-scaffold-example-{}.txt", std::process::id()));
+        let (input, example) = (input.to_str().unwrap(), example.to_str().unwrap());
+        std::fs::write(input, "real input\n").unwrap();
+        let _ = std::fs::remove_file(example);
+
+        assert_eq!(
+            scaffold_data_files(input, example),
+            [FileStatus::Kept, FileStatus::Created]
+        );
+        assert_eq!(std::fs::read_to_string(input).unwrap(), "real input\n");
+        assert_eq!(std::fs::read_to_string(example).unwrap(), "");
+
+        std::fs::remove_file(input).unwrap();
+        std::fs::remove_file(example).unwrap();
+    }
+}
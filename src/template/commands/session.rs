@@ -0,0 +1,108 @@
+use std::{
+    env,
+    io::{self, Write},
+    process,
+};
+
+use crate::template::aoc_client::{self, AocClient, AocClientError};
+
+/// Accepts either the bare cookie value or a `session=<value>` pair as copied from the browser.
+fn normalize_session(input: &str) -> Option<String> {
+    let session = input.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+    let session = session.split(';').next().unwrap_or_default().trim();
+
+    if session.is_empty() || !session.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some(session.to_string())
+}
+
+/// Stores a session cookie, read from stdin if not passed as an argument, and validates it.
+pub fn login(session: Option<String>) {
+    let input = session.unwrap_or_else(|| {
+        print!("Paste your session cookie: ");
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        let _ = io::stdin().read_line(&mut line);
+        line
+    });
+
+    let Some(session) = normalize_session(&input) else {
+        eprintln!("That does not look like a session cookie. Copy the value of the `session` cookie from your browser.");
+        process::exit(1);
+    };
+
+    match aoc_client::store_session(&session) {
+        Ok(path) => println!("🎄 Stored session cookie in \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("failed to store session cookie: {e}");
+            process::exit(1);
+        }
+    }
+
+    if env::var("ADVENT_OF_CODE_SESSION").is_ok() {
+        println!(
+            "Note: ADVENT_OF_CODE_SESSION is set and takes precedence over the stored cookie."
+        );
+    }
+
+    status();
+}
+
+/// Reports where the session cookie is loaded from and whether the website accepts it.
+pub fn status() {
+    if env::var("ADVENT_OF_CODE_SESSION").is_ok() {
+        println!("Session cookie: ADVENT_OF_CODE_SESSION environment variable.");
+    } else if let Some(path) = aoc_client::get_session_path().filter(|p| p.exists()) {
+        println!("Session cookie: \"{}\".", path.display());
+        warn_about_permissions(&path);
+    }
+
+    let checked = AocClient::from_env().and_then(|client| client.check_session());
+
+    match checked {
+        Ok(()) => println!("Session status: ✔ valid"),
+        Err(AocClientError::SessionExpired) => {
+            println!("Session status: ✖ expired or invalid. Run `cargo session login` to store a new one.");
+            process::exit(1);
+        }
+        Err(e) => {
+            println!("Session status: ✖ {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn warn_about_permissions(path: &std::path::Path) {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Ok(metadata) = path.metadata() {
+        if metadata.permissions().mode() & 0o077 != 0 {
+            println!(
+                "Warning: the session file is readable by other users. Run `chmod 600 {}` or `cargo session login` to fix this.",
+                path.display()
+            );
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn warn_about_permissions(_path: &std::path::Path) {}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::normalize_session;
+
+    #[test]
+    fn normalizes_session_input() {
+        assert_eq!(normalize_session("abc123\n"), Some("abc123".into()));
+        assert_eq!(
+            normalize_session("session=abc123; Path=/"),
+            Some("abc123".into())
+        );
+        assert_eq!(normalize_session("  \n"), None);
+        assert_eq!(normalize_session("not a cookie"), None);
+    }
+}
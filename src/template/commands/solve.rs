@@ -1,19 +1,21 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
-
-pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Option<u8>) {
+use crate::template::{build_options::BuildOptions, Day};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    time: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    options: &BuildOptions,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+        cmd_args.extend(options.with_dhat().cargo_args(false));
+    } else {
+        cmd_args.extend(options.cargo_args(release));
     }
 
     cmd_args.push("--".to_string());
@@ -27,7 +29,10 @@ pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Opti
         cmd_args.push("--time".to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    let mut cmd = Command::new("cargo");
+    options.apply_env(&mut cmd);
+
+    let mut cmd = cmd
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
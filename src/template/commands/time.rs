@@ -2,37 +2,48 @@ use std::collections::HashSet;
 
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, benchmark_chart, build_options::BuildOptions, readme_benchmarks, Day,
+};
 
-pub fn handle(day: Option<Day>, recreate_all: bool) {
+pub fn handle(day: Option<Day>, recreate_all: bool, options: &BuildOptions) {
     let stored_timings = Timings::read_from_file();
+    let build = options.label();
 
     let days_to_run = day.map_or_else(
         || {
             if recreate_all {
                 all_days().collect()
             } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
+                // when the `--all` flag is not set, filter out days that are fully benched
+                // with the same build options.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| {
+                        !stored_timings.is_day_complete(*day)
+                            || !stored_timings.is_day_built_with(*day, build.as_deref())
+                    })
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, options).unwrap();
 
     let merged_timings = stored_timings.merge(&timings);
     merged_timings.store_file().unwrap();
 
     println!();
+    if let Err(e) = benchmark_chart::store(&merged_timings) {
+        eprintln!("Failed to store benchmark chart: {e}");
+    }
+
     match readme_benchmarks::update(merged_timings) {
         Ok(()) => {
             println!("Stored updated benchmarks.");
         }
-        Err(_) => {
-            eprintln!("Failed to store updated benchmarks.");
+        Err(e) => {
+            eprintln!("Failed to store updated benchmarks: {e}");
         }
     }
 }
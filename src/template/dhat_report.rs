@@ -0,0 +1,196 @@
+/// Module that reads back the DHAT reports written by `--dhat` runs and summarises their heap stats.
+use std::{collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{leaderboard, Day};
+
+/// Path of the DHAT report for one part of a day, e.g. `dhat-heap-01-1.json`.
+#[must_use]
+pub fn get_report_path(day: Day, part: u8) -> String {
+    format!("dhat-heap-{day}-{part}.json")
+}
+
+/// Key totals of a DHAT heap profile.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeapSummary {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    /// Bytes that were allocated at the time of the global heap maximum (t-gmax).
+    pub max_bytes: u64,
+    pub max_blocks: u64,
+}
+
+impl HeapSummary {
+    /// Reads the report of one part, `None` if there is no report.
+    pub fn read(day: Day, part: u8) -> Option<Result<Self, String>> {
+        let report = fs::read_to_string(get_report_path(day, part)).ok()?;
+        Some(HeapSummary::try_from(report))
+    }
+
+    /// Formats the summary on a single line, e.g. `1.5 KiB at t-gmax (2 blocks), 2.0 KiB in 3 blocks in total`.
+    pub fn to_compact_string(&self) -> String {
+        format!(
+            "{} at t-gmax ({} blocks), {} in {} blocks in total",
+            format_bytes(self.max_bytes),
+            self.max_blocks,
+            format_bytes(self.total_bytes),
+            self.total_blocks
+        )
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+/// Renders the summaries of several days as a table, one row per part.
+pub fn table(summaries: &[(Day, u8, HeapSummary)]) -> String {
+    let rows: Vec<Vec<String>> = summaries
+        .iter()
+        .map(|(day, part, summary)| {
+            vec![
+                day.to_string(),
+                part.to_string(),
+                format_bytes(summary.max_bytes),
+                summary.max_blocks.to_string(),
+                format_bytes(summary.total_bytes),
+                summary.total_blocks.to_string(),
+            ]
+        })
+        .collect();
+
+    leaderboard::table(
+        &[
+            "Day",
+            "Part",
+            "At t-gmax",
+            "Blocks",
+            "Total",
+            "Total blocks",
+        ],
+        &rows,
+        false,
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn get_number(pp: &HashMap<String, JsonValue>, key: &str) -> u64 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pp.get(key)
+        .and_then(|v| v.get::<f64>())
+        .map_or(0, |x| *x as u64)
+}
+
+impl TryFrom<String> for HeapSummary {
+    type Error = String;
+
+    /// Sums up the program points (`pps`) of a DHAT report.
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let pps = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("pps")
+            .ok_or("expected JSON document to have key `pps`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.pps` to be an array.")?;
+
+        pps.iter()
+            .try_fold(HeapSummary::default(), |mut summary, pp| {
+                let pp = pp
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("expected program point to be an object.")?;
+
+                summary.total_bytes += get_number(pp, "tb");
+                summary.total_blocks += get_number(pp, "tbk");
+                summary.max_bytes += get_number(pp, "gb");
+                summary.max_blocks += get_number(pp, "gbk");
+                Ok(summary)
+            })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, get_report_path, table, HeapSummary};
+    use crate::day;
+
+    const REPORT: &str = r#"{
+        "dhatFileVersion": 2, "mode": "rust-heap", "verb": "Allocated", "bklt": true, "bkacc": false,
+        "tu": "µs", "Mtu": "s", "tuth": 10, "cmd": "target/dhat/01", "pid": 1, "tg": 120, "te": 300,
+        "pps": [
+            { "tb": 1024, "tbk": 2, "tl": 10, "mb": 1024, "mbk": 2, "gb": 512, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1] },
+            { "tb": 2048, "tbk": 5, "tl": 10, "mb": 1024, "mbk": 1, "gb": 1024, "gbk": 1, "eb": 0, "ebk": 0, "fs": [2] }
+        ],
+        "ftbl": ["[root]", "a", "b"]
+    }"#;
+
+    #[test]
+    fn sums_program_points() {
+        let summary = HeapSummary::try_from(REPORT.to_string()).unwrap();
+        assert_eq!(
+            summary,
+            HeapSummary {
+                total_bytes: 3072,
+                total_blocks: 7,
+                max_bytes: 1536,
+                max_blocks: 2,
+            }
+        );
+        assert_eq!(
+            summary.to_compact_string(),
+            "1.5 KiB at t-gmax (2 blocks), 3.0 KiB in 7 blocks in total"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_reports() {
+        assert!(HeapSummary::try_from("{}".to_string()).is_err());
+        assert!(HeapSummary::try_from("dhat".to_string()).is_err());
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn renders_table() {
+        assert_eq!(get_report_path(day!(1), 2), "dhat-heap-01-2.json");
+
+        let summary = HeapSummary::try_from(REPORT.to_string()).unwrap();
+        let rendered = table(&[(day!(1), 1, summary)]);
+        assert_eq!(
+            rendered,
+            [
+                "Day  Part  At t-gmax  Blocks  Total    Total blocks",
+                "01   1     1.5 KiB    2       3.0 KiB  7",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,424 @@
+/// Module that reads the JSON export of a private leaderboard and renders it as tables.
+/// The format is documented on the private leaderboard page of the Advent of Code website.
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+pub static LEADERBOARD_FILE_PATH: &str = "./data/leaderboard.json";
+
+/// A member of a private leaderboard.
+#[derive(Clone, Debug)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub stars: u64,
+    pub local_score: u64,
+    /// Completion timestamps (unix seconds) per day, for part one and part two.
+    pub completions: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+/// A private leaderboard for a single event.
+#[derive(Clone, Debug)]
+pub struct Leaderboard {
+    pub event: u16,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// Days that at least one member has collected a star for.
+    pub fn days(&self) -> BTreeSet<u8> {
+        self.members
+            .iter()
+            .flat_map(|m| m.completions.keys().copied())
+            .collect()
+    }
+
+    /// Local score points per member and day.
+    /// For each part, the first member to finish gets one point per member, the second one point less, and so on.
+    pub fn points(&self) -> HashMap<u64, BTreeMap<u8, u64>> {
+        let member_count = self.members.len() as u64;
+        let mut points: HashMap<u64, BTreeMap<u8, u64>> = HashMap::new();
+
+        for day in self.days() {
+            for part in 0..2 {
+                let mut finishers: Vec<(u64, u64)> = self
+                    .members
+                    .iter()
+                    .filter_map(|m| Some((m.completions.get(&day)?[part]?, m.id)))
+                    .collect();
+                finishers.sort_unstable();
+
+                for (rank, (_, id)) in finishers.into_iter().enumerate() {
+                    *points.entry(id).or_default().entry(day).or_default() +=
+                        member_count - rank as u64;
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Members ordered by local score, then by stars.
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.name.cmp(&b.name))
+        });
+        members
+    }
+
+    /// Unix timestamp of the puzzle unlock for `day`, midnight EST.
+    pub fn unlock_timestamp(&self, day: u8) -> u64 {
+        days_from_civil(i64::from(self.event), 12, i64::from(day)) as u64 * 86_400 + 5 * 3_600
+    }
+}
+
+/// Days since the unix epoch for a date in the proleptic gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Formats a number of seconds as `hh:mm:ss`, prefixed with the number of days if necessary.
+pub fn format_elapsed(seconds: u64) -> String {
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3_600,
+        rest % 3_600 / 60,
+        rest % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders rows as an aligned plain-text table, or as a markdown table.
+pub fn table(headers: &[&str], rows: &[Vec<String>], markdown: bool) -> String {
+    if markdown {
+        let mut lines = vec![
+            format!("| {} |", headers.join(" | ")),
+            format!("|{}", " :---: |".repeat(headers.len())),
+        ];
+        lines.extend(rows.iter().map(|row| format!("| {} |", row.join(" | "))));
+        return lines.join("\n");
+    }
+
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([headers[i].chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(headers.to_vec())];
+    lines.extend(
+        rows.iter()
+            .map(|row| format_row(row.iter().map(String::as_str).collect())),
+    );
+    lines.join("\n")
+}
+
+/// Renders standings and per-day completion times, as plain text or as markdown.
+/// The score delta is the number of points each member gained on the most recent day.
+pub fn render(leaderboard: &Leaderboard, markdown: bool) -> String {
+    let points = leaderboard.points();
+    let latest_day = leaderboard.days().last().copied();
+    let heading = |s: String| if markdown { format!("## {s}") } else { s };
+
+    let standings: Vec<Vec<String>> = leaderboard
+        .standings()
+        .iter()
+        .enumerate()
+        .map(|(i, member)| {
+            let delta = latest_day
+                .and_then(|day| points.get(&member.id)?.get(&day).copied())
+                .unwrap_or(0);
+
+            vec![
+                format!("{}", i + 1),
+                member.name.clone(),
+                member.local_score.to_string(),
+                format!("+{delta}"),
+                member.stars.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut sections = vec![
+        heading(format!(
+            "Private leaderboard {} ({} members)",
+            leaderboard.event,
+            leaderboard.members.len()
+        )),
+        table(&["#", "Name", "Score", "Δ", "Stars"], &standings, markdown),
+    ];
+
+    for day in leaderboard.days() {
+        let unlock = leaderboard.unlock_timestamp(day);
+
+        let mut finishers: Vec<(&Member, [Option<u64>; 2])> = leaderboard
+            .members
+            .iter()
+            .filter_map(|m| Some((m, *m.completions.get(&day)?)))
+            .collect();
+        finishers.sort_by_key(|(_, [one, two])| (two.is_none(), *two, *one));
+
+        let rows: Vec<Vec<String>> = finishers
+            .iter()
+            .map(|(member, [one, two])| {
+                let elapsed = |ts: &Option<u64>| {
+                    ts.map_or("-".into(), |ts| format_elapsed(ts.saturating_sub(unlock)))
+                };
+                let between = match (one, two) {
+                    (Some(one), Some(two)) => format_elapsed(two.saturating_sub(*one)),
+                    _ => "-".into(),
+                };
+                let day_points = points
+                    .get(&member.id)
+                    .and_then(|p| p.get(&day))
+                    .copied()
+                    .unwrap_or(0);
+
+                vec![
+                    member.name.clone(),
+                    elapsed(one),
+                    elapsed(two),
+                    between,
+                    format!("+{day_points}"),
+                ]
+            })
+            .collect();
+
+        sections.push(heading(format!("Day {day}")));
+        sections.push(table(
+            &["Name", "Part 1", "Part 2", "Part 1 → 2", "Points"],
+            &rows,
+            markdown,
+        ));
+    }
+
+    sections.join("\n\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn get_u64(json: &HashMap<String, JsonValue>, key: &str) -> Option<u64> {
+    match json.get(key)? {
+        JsonValue::Number(n) => Some(*n as u64),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let event = get_u64(json, "event").ok_or("expected `json.event` to be a year.")?;
+
+        let members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?;
+
+        Ok(Leaderboard {
+            event: event as u16,
+            members: members
+                .values()
+                .map(Member::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let id = get_u64(json, "id").ok_or("Expected member.id to be a number.")?;
+
+        let name = match json.get("name").and_then(|v| v.get::<String>()) {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{id})"),
+        };
+
+        let stars = get_u64(json, "stars").ok_or("Expected member.stars to be a number.")?;
+        let local_score =
+            get_u64(json, "local_score").ok_or("Expected member.local_score to be a number.")?;
+
+        let days = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        let mut completions = BTreeMap::new();
+
+        for (day, parts) in days {
+            let day: u8 = day
+                .parse()
+                .map_err(|_| "Expected completion_day_level keys to be days.")?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected completion_day_level values to be objects.")?;
+
+            let timestamp = |part: &str| {
+                parts
+                    .get(part)
+                    .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                    .and_then(|star| get_u64(star, "get_star_ts"))
+            };
+
+            completions.insert(day, [timestamp("1"), timestamp("2")]);
+        }
+
+        Ok(Member {
+            id,
+            name,
+            stars,
+            local_score,
+            completions,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{days_from_civil, format_elapsed, render, Leaderboard};
+
+    // 2023-12-01 05:00:00 UTC
+    const UNLOCK: u64 = 1_701_406_800;
+
+    fn get_mock_leaderboard() -> Leaderboard {
+        let json = format!(
+            r#"{{
+                "event": "2023",
+                "owner_id": 1,
+                "members": {{
+                    "1": {{ "id": 1, "name": "alice", "stars": 2, "local_score": 6, "global_score": 0, "last_star_ts": {b},
+                        "completion_day_level": {{ "1": {{ "1": {{ "get_star_ts": {a}, "star_index": 1 }}, "2": {{ "get_star_ts": {b}, "star_index": 3 }} }} }} }},
+                    "2": {{ "id": 2, "name": null, "stars": 1, "local_score": 2, "global_score": 0, "last_star_ts": {c},
+                        "completion_day_level": {{ "1": {{ "1": {{ "get_star_ts": {c}, "star_index": 2 }} }} }} }},
+                    "3": {{ "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0, "last_star_ts": 0,
+                        "completion_day_level": {{}} }}
+                }}
+            }}"#,
+            a = UNLOCK + 300,
+            b = UNLOCK + 900,
+            c = UNLOCK + 90_061,
+        );
+        Leaderboard::try_from(json).unwrap()
+    }
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(leaderboard.event, 2023);
+        assert_eq!(leaderboard.members.len(), 3);
+
+        let standings = leaderboard.standings();
+        assert_eq!(standings[0].name, "alice");
+        assert_eq!(standings[1].name, "(anonymous user #2)");
+        assert_eq!(
+            standings[0].completions[&1],
+            [Some(UNLOCK + 300), Some(UNLOCK + 900)]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(Leaderboard::try_from("{}".to_string()).is_err());
+        assert!(Leaderboard::try_from("foo".to_string()).is_err());
+    }
+
+    #[test]
+    fn computes_points() {
+        let points = get_mock_leaderboard().points();
+        assert_eq!(points[&1][&1], 6);
+        assert_eq!(points[&2][&1], 2);
+        assert!(!points.contains_key(&3));
+    }
+
+    #[test]
+    fn computes_unlock_time() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(get_mock_leaderboard().unlock_timestamp(1), UNLOCK);
+    }
+
+    #[test]
+    fn formats_elapsed_time() {
+        assert_eq!(format_elapsed(0), "00:00:00");
+        assert_eq!(format_elapsed(3_723), "01:02:03");
+        assert_eq!(format_elapsed(90_061), "1d 01:01:01");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let expected = [
+            "## Private leaderboard 2023 (3 members)",
+            "",
+            "| # | Name | Score | Δ | Stars |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| 1 | alice | 6 | +6 | 2 |",
+            "| 2 | (anonymous user #2) | 2 | +2 | 1 |",
+            "| 3 | carol | 0 | +0 | 0 |",
+            "",
+            "## Day 1",
+            "",
+            "| Name | Part 1 | Part 2 | Part 1 → 2 | Points |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| alice | 00:05:00 | 00:15:00 | 00:10:00 | +6 |",
+            "| (anonymous user #2) | 1d 01:01:01 | - | - | +2 |",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(render(&get_mock_leaderboard(), true), expected);
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        let rendered = render(&get_mock_leaderboard(), false);
+        assert!(rendered.starts_with("Private leaderboard 2023 (3 members)\n\n#  Name"));
+        assert!(rendered.contains("\n1  alice                6      +6  2\n"));
+    }
+}
@@ -1,15 +1,29 @@
 use std::{env, fs};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod build_options;
+pub mod checksums;
 pub mod commands;
+pub mod leaderboard;
+pub mod puzzle;
 pub mod runner;
 
 pub use day::*;
 
+mod benchmark_chart;
+mod bin_path;
 mod day;
+mod dhat_report;
+mod placeholders;
+mod puzzle_renderer;
+mod readme_badges;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod vscode_launch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -62,6 +76,7 @@ macro_rules! solution {
         fn main() {
             use $crate::template::runner::*;
             let input = $crate::template::read_file("inputs", DAY);
+            $crate::template::checksums::verify_input(DAY, &input);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
@@ -0,0 +1,216 @@
+/// A small substitution engine for solution templates.
+///
+/// Placeholders like `%YEAR%` are replaced with their value. Lines consisting of `%IF NAME%`, `%IF !NAME%`,
+/// `%ELSE%` and `%ENDIF%` mark conditional blocks, which are kept or dropped depending on whether `NAME` is set.
+/// Other text, including unknown placeholders and `%` signs in code, is left untouched.
+use std::collections::{HashMap, HashSet};
+
+use crate::template::{aoc_cli::get_year, puzzle, Day};
+
+#[derive(Debug, Default)]
+pub struct Context {
+    values: HashMap<String, String>,
+    flags: HashSet<String>,
+}
+
+impl Context {
+    /// Sets the value of the placeholder `%{name}%`.
+    pub fn set(&mut self, name: &str, value: impl Into<String>) -> &mut Self {
+        self.values.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets a flag that can be tested with `%IF {name}%`.
+    pub fn flag(&mut self, name: &str, enabled: bool) -> &mut Self {
+        if enabled {
+            self.flags.insert(name.into());
+        } else {
+            self.flags.remove(name);
+        }
+        self
+    }
+
+    /// The placeholders available when scaffolding `day`:
+    /// `%DAY_NUMBER%`, `%DAY_PADDED%`, `%YEAR%`, `%DATE%`, `%PUZZLE_URL%` and `%TITLE%`.
+    /// Flags are set for `TITLE` (the puzzle was downloaded), `YEAR` (`AOC_YEAR` is set)
+    /// and `SECOND_EXAMPLE` (`NN-2.txt` exists).
+    pub fn for_day(day: Day) -> Self {
+        let mut context = Context::default();
+        let title = puzzle::read_puzzle(day).and_then(|markdown| puzzle::title(&markdown));
+
+        context
+            .set("DAY_NUMBER", day.into_inner().to_string())
+            .set("DAY_PADDED", day.to_string())
+            .set("TITLE", title.clone().unwrap_or_default())
+            .flag("TITLE", title.is_some())
+            .flag(
+                "SECOND_EXAMPLE",
+                std::path::Path::new(&format!("data/examples/{day}-2.txt")).exists(),
+            );
+
+        if let Some(year) = get_year() {
+            context
+                .flag("YEAR", true)
+                .set("YEAR", year.to_string())
+                .set("DATE", format!("{year}-12-{day}"))
+                .set(
+                    "PUZZLE_URL",
+                    format!("https://adventofcode.com/{year}/day/{}", day.into_inner()),
+                );
+        }
+
+        context
+    }
+}
+
+fn parse_directive(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let inner = line.strip_prefix('%')?.strip_suffix('%')?;
+
+    match inner {
+        "ELSE" | "ENDIF" => Some(inner),
+        _ if inner.starts_with("IF ") => Some(inner),
+        _ => None,
+    }
+}
+
+/// Renders `template` with the values and flags of `context`.
+pub fn render(template: &str, context: &Context) -> Result<String, String> {
+    // one entry per open `%IF%` block: whether its current branch is included.
+    let mut stack: Vec<bool> = vec![];
+    let mut out = String::new();
+
+    for (i, line) in template.split_inclusive('\n').enumerate() {
+        let included = stack.iter().all(|x| *x);
+
+        match parse_directive(line) {
+            Some("ELSE") => {
+                let branch = stack
+                    .last_mut()
+                    .ok_or(format!("line {}: `%ELSE%` without `%IF%`.", i + 1))?;
+                *branch = !*branch;
+            }
+            Some("ENDIF") => {
+                stack
+                    .pop()
+                    .ok_or(format!("line {}: `%ENDIF%` without `%IF%`.", i + 1))?;
+            }
+            Some(directive) => {
+                let condition = directive.trim_start_matches("IF ").trim();
+                let enabled = match condition.strip_prefix('!') {
+                    Some(name) => !context.flags.contains(name.trim()),
+                    None => context.flags.contains(condition),
+                };
+                stack.push(enabled);
+            }
+            None if included => out.push_str(&substitute(line, &context.values)),
+            None => {}
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err("unterminated `%IF%` block, expected `%ENDIF%`.".into());
+    }
+
+    Ok(out)
+}
+
+/// Replaces `%NAME%` placeholders in a single left-to-right pass, so inserted values are never scanned again.
+/// Text between two `%` that is not a known name, like in `a % 2`, is kept as is.
+fn substitute(line: &str, values: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(start) = rest.find('%') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        match after
+            .find('%')
+            .and_then(|end| Some((end, values.get(&after[..end])?)))
+        {
+            Some((end, value)) => {
+                out.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('%');
+                rest = after;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Context};
+
+    fn get_mock_context() -> Context {
+        let mut context = Context::default();
+        context
+            .set("DAY_NUMBER", "5")
+            .set("TITLE", "If You Give A Seed A Fertilizer")
+            .flag("TITLE", true);
+        context
+    }
+
+    #[test]
+    fn replaces_placeholders() {
+        let rendered = render(
+            "// Day %DAY_NUMBER%: %TITLE%\nlet x = a % 2; // %UNKNOWN%\n",
+            &get_mock_context(),
+        );
+        assert_eq!(
+            rendered.unwrap(),
+            "// Day 5: If You Give A Seed A Fertilizer\nlet x = a % 2; // %UNKNOWN%\n"
+        );
+    }
+
+    #[test]
+    fn does_not_substitute_inserted_values() {
+        let mut context = get_mock_context();
+        context.set("TITLE", "100% %DAY_NUMBER%");
+        assert_eq!(
+            render("%TITLE% %%DAY_NUMBER%\n", &context).unwrap(),
+            "100% %DAY_NUMBER% %5\n"
+        );
+    }
+
+    #[test]
+    fn renders_conditional_blocks() {
+        let template = [
+            "a",
+            "%IF TITLE%",
+            "b",
+            "%IF SECOND_EXAMPLE%",
+            "c",
+            "%ELSE%",
+            "d",
+            "%ENDIF%",
+            "%ELSE%",
+            "e",
+            "%ENDIF%",
+            "    %IF !SECOND_EXAMPLE%",
+            "f",
+            "    %ENDIF%",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            render(&template, &get_mock_context()).unwrap(),
+            "a\nb\nd\nf\n"
+        );
+    }
+
+    #[test]
+    fn rejects_unbalanced_blocks() {
+        let context = get_mock_context();
+        assert!(render("%IF TITLE%\na\n", &context).is_err());
+        assert!(render("a\n%ENDIF%\n", &context).is_err());
+        assert!(render("%ELSE%\n", &context).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+/// Helpers for reading the puzzle descriptions stored in `data/puzzles/`.
+/// Both the markdown written by the native client and the one written by aoc-cli are supported.
+use std::fs;
+
+use crate::template::{aoc_cli::get_puzzle_path, aoc_client::decode_entities, Day};
+
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// Reads the stored puzzle description for `day`.
+pub fn read_puzzle(day: Day) -> Option<String> {
+    fs::read_to_string(get_puzzle_path(day)).ok()
+}
+
+pub fn get_previous_puzzle_path(day: Day) -> String {
+    format!("data/puzzles/{day}.previous.md")
+}
+
+/// Reads the puzzle description for `day` as it was before it last changed, e.g. before part two was unlocked.
+pub fn read_previous_puzzle(day: Day) -> Option<String> {
+    fs::read_to_string(get_previous_puzzle_path(day)).ok()
+}
+
+/// Runs `download`, which may replace the stored puzzle description for `day`.
+/// If the description changed, the replaced version is kept in `data/puzzles/{day}.previous.md`.
+pub fn keep_previous<T, E>(day: Day, download: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    let before = read_puzzle(day).filter(|s| !s.is_empty());
+    let result = download();
+
+    if let Some(before) = before {
+        if read_puzzle(day).is_some_and(|after| after != before) {
+            if let Err(e) = fs::write(get_previous_puzzle_path(day), before) {
+                eprintln!("failed to keep previous puzzle description: {e}");
+            }
+        }
+    }
+
+    result
+}
+
+/// Splits a puzzle description into the sections for part one and (if unlocked) part two.
+pub fn split_parts(markdown: &str) -> (&str, Option<&str>) {
+    match markdown.find(PART_TWO_HEADING) {
+        Some(pos) => {
+            // include the heading line in the second section.
+            let start = markdown[..pos].rfind('\n').map_or(0, |x| x + 1);
+            (&markdown[..start], Some(&markdown[start..]))
+        }
+        None => (markdown, None),
+    }
+}
+
+/// Returns the puzzle title from a `--- Day 1: Trebuchet?! ---` heading.
+pub fn title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let heading = line.trim_start_matches(['#', '\\', ' ']);
+        let heading = heading
+            .strip_prefix("--- Day ")?
+            .trim_end()
+            .strip_suffix("---")?;
+        let (_, title) = heading.split_once(':')?;
+        Some(title.trim().to_string())
+    })
+}
+
+fn strip_tags(s: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// A piece of a puzzle description: either a block of prose or a code block.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Block {
+    Text(String),
+    Code(String),
+}
+
+impl Block {
+    fn to_markdown(&self) -> String {
+        match self {
+            Block::Text(text) => text.clone(),
+            Block::Code(code) => format!("```\n{code}```"),
+        }
+    }
+}
+
+/// Splits a section into paragraphs and code blocks.
+/// Code blocks are either fenced with ```` ``` ```` or wrapped in raw `<pre><code>` tags.
+pub fn blocks(section: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut text: Vec<&str> = vec![];
+    let mut code: Option<Vec<&str>> = None;
+    let mut html_code = false;
+
+    let flush_text = |text: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !text.is_empty() {
+            blocks.push(Block::Text(text.join("\n")));
+            text.clear();
+        }
+    };
+
+    for line in section.lines() {
+        if let Some(lines) = &mut code {
+            let is_end = if html_code {
+                line.contains("</code></pre>")
+            } else {
+                line.trim_start().starts_with("```")
+            };
+
+            if is_end {
+                if html_code {
+                    lines.push(line.split("</code></pre>").next().unwrap_or_default());
+                }
+                let content = lines.join("\n");
+                let content = if html_code {
+                    decode_entities(&strip_tags(&content))
+                } else {
+                    content
+                };
+                blocks.push(Block::Code(format!("{}\n", content.trim_end_matches('\n'))));
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if line.trim_start().starts_with("```") {
+            flush_text(&mut text, &mut blocks);
+            html_code = false;
+            code = Some(vec![]);
+        } else if let Some(rest) = line.trim_start().strip_prefix("<pre><code>") {
+            flush_text(&mut text, &mut blocks);
+            html_code = true;
+            if let Some(content) = rest.strip_suffix("</code></pre>") {
+                let content = decode_entities(&strip_tags(content));
+                blocks.push(Block::Code(format!("{content}\n")));
+            } else {
+                code = Some(vec![rest]);
+            }
+        } else if line.trim().is_empty() {
+            flush_text(&mut text, &mut blocks);
+        } else {
+            text.push(line);
+        }
+    }
+
+    flush_text(&mut text, &mut blocks);
+    blocks
+}
+
+/// Returns the contents of all code blocks in a section.
+pub fn code_blocks(section: &str) -> Vec<String> {
+    blocks(section)
+        .into_iter()
+        .filter_map(|block| match block {
+            Block::Code(code) => Some(code),
+            Block::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Returns all emphasised code spans in a piece of text, i.e. ``*`x`*``, `` `*x*` `` or `<code><em>x</em></code>`.
+pub fn emphasised_code(text: &str) -> Vec<String> {
+    let mut spans = vec![];
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let candidates = [
+            ("*`", "`*"),
+            ("`*", "*`"),
+            ("<code><em>", "</em></code>"),
+            ("<em><code>", "</code></em>"),
+        ];
+
+        let next = candidates
+            .iter()
+            .filter_map(|(open, close)| rest.find(open).map(|pos| (pos, *open, *close)))
+            .min_by_key(|(pos, _, _)| *pos);
+
+        let Some((pos, open, close)) = next else {
+            break;
+        };
+
+        let start = pos + open.len();
+        let Some(len) = rest[start..].find(close) else {
+            break;
+        };
+
+        spans.push(decode_entities(&rest[start..start + len]));
+        rest = &rest[start + len + close.len()..];
+    }
+
+    spans
+}
+
+/// Returns candidate answers for the example in a section: emphasised code in paragraphs that mention an example.
+pub fn example_answers(section: &str) -> Vec<String> {
+    blocks(section)
+        .into_iter()
+        .filter_map(|block| match block {
+            Block::Text(text) if text.to_lowercase().contains("example") => Some(text),
+            _ => None,
+        })
+        .flat_map(|text| emphasised_code(&text))
+        .collect()
+}
+
+/// Returns the blocks of `current` that are not part of `previous`, e.g. the text added when part two unlocked.
+pub fn added_blocks(previous: &str, current: &str) -> String {
+    let previous = blocks(previous);
+
+    blocks(current)
+        .into_iter()
+        .filter(|block| !previous.contains(block))
+        .map(|block| block.to_markdown())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        added_blocks, blocks, code_blocks, emphasised_code, example_answers, split_parts, title,
+        Block,
+    };
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow production.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values of these lines are `12` and `38`. Adding these together produces *`50`*.
+
+Your puzzle answer was `54953`.
+
+## --- Part Two ---
+
+Your calculation isn't quite right.
+
+<pre><code>two1nine
+eight&lt;wothree
+</code></pre>
+
+In this example, the calibration values are `29` and `83`. Adding these together produces `*112*`.
+";
+
+    #[test]
+    fn splits_parts() {
+        let (one, two) = split_parts(PUZZLE);
+        assert!(one.starts_with("## --- Day 1"));
+        assert!(!one.contains("Part Two"));
+        assert!(two.unwrap().starts_with("## --- Part Two ---"));
+        assert_eq!(split_parts("foo"), ("foo", None));
+    }
+
+    #[test]
+    fn finds_title() {
+        assert_eq!(title(PUZZLE), Some("Trebuchet?!".into()));
+        assert_eq!(
+            title("\\--- Day 5: If You Give A Seed A Fertilizer ---\n----------"),
+            Some("If You Give A Seed A Fertilizer".into())
+        );
+        assert_eq!(title("## --- Part Two ---"), None);
+    }
+
+    #[test]
+    fn finds_code_blocks() {
+        let (one, two) = split_parts(PUZZLE);
+        assert_eq!(code_blocks(one), vec!["1abc2\npqr3stu8vwx\n"]);
+        assert_eq!(code_blocks(two.unwrap()), vec!["two1nine\neight<wothree\n"]);
+    }
+
+    #[test]
+    fn finds_single_line_html_code_blocks() {
+        assert_eq!(
+            blocks("foo\n<pre><code>a &amp; b</code></pre>\nbar"),
+            vec![
+                Block::Text("foo".into()),
+                Block::Code("a & b\n".into()),
+                Block::Text("bar".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_emphasised_code() {
+        assert_eq!(
+            emphasised_code("a *`1`* b `*2*` c <code><em>3</em></code> `4` *5*"),
+            vec!["1", "2", "3"]
+        );
+    }
+
+    #[test]
+    fn finds_example_answers() {
+        let (one, two) = split_parts(PUZZLE);
+        assert_eq!(example_answers(one), vec!["50"]);
+        assert_eq!(example_answers(two.unwrap()), vec!["112"]);
+    }
+
+    #[test]
+    fn finds_added_blocks() {
+        let (one, _) = split_parts(PUZZLE);
+        let added = added_blocks(one, PUZZLE);
+        assert!(added.starts_with("## --- Part Two ---\n\nYour calculation isn't quite right.\n\n```\ntwo1nine\neight<wothree\n```"));
+        assert!(!added.contains("Day 1"));
+        assert_eq!(added_blocks(PUZZLE, PUZZLE), "");
+    }
+}
@@ -0,0 +1,327 @@
+/// Renders the markdown of a puzzle description for the terminal.
+/// Supports the subset of markdown that the native client and aoc-cli produce for puzzle pages.
+use crate::template::puzzle::{self, Block};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const ANSI_HIGHLIGHT: &str = "\x1b[1;33m";
+const DEFAULT_WIDTH: usize = 80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Italic,
+    Bold,
+    Highlight,
+}
+
+impl Style {
+    fn ansi(self) -> &'static str {
+        match self {
+            Style::Plain => "",
+            Style::Italic => ANSI_ITALIC,
+            Style::Bold => ANSI_BOLD,
+            Style::Highlight => ANSI_HIGHLIGHT,
+        }
+    }
+}
+
+/// Returns the width of the attached terminal, or a sensible default when output is redirected.
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size().map_or(DEFAULT_WIDTH, |(w, _)| usize::from(w.0))
+}
+
+/// Returns `true` if `marker` occurs again on the current line of `rest`.
+fn closed_on_line(rest: &str, marker: &str) -> bool {
+    rest.lines()
+        .next()
+        .is_some_and(|line| line.contains(marker))
+}
+
+/// Returns `true` if `rest`, which follows a `[`, continues as a link on the current line.
+fn is_link(rest: &str) -> bool {
+    rest.lines()
+        .next()
+        .and_then(|line| line.split_once(']'))
+        .is_some_and(|(text, target)| {
+            !text.contains('[') && target.starts_with('(') && target.contains(')')
+        })
+}
+
+/// Parses inline markdown into styled characters.
+/// A `*` or `**` only opens emphasis if it is closed on the same line, so lone asterisks like in `2 * 3` stay text.
+fn parse_inline(text: &str, base: Style) -> Vec<(char, Style)> {
+    let mut out = vec![];
+    let mut bold = false;
+    let mut italic = false;
+    let mut link = false;
+    let mut rest = text;
+
+    let style = |bold: bool, italic: bool| match (bold, italic) {
+        (true, _) => Style::Bold,
+        (false, true) => Style::Italic,
+        _ => base,
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let emphasised_code = [("*`", "`*"), ("`*", "*`")]
+            .iter()
+            .find_map(|(open, close)| {
+                let inner = rest.strip_prefix(open)?;
+                inner
+                    .find(close)
+                    .map(|len| (&inner[..len], open.len() + len + close.len()))
+            });
+
+        if let Some((code, len)) = emphasised_code {
+            out.extend(code.chars().map(|c| (c, Style::Highlight)));
+            rest = &rest[len..];
+        } else if let Some((code, _)) = rest.strip_prefix('`').and_then(|r| r.split_once('`')) {
+            let current = style(bold, italic);
+            out.extend(code.chars().map(|c| (c, current)));
+            rest = &rest[code.len() + 2..];
+        } else if let Some(r) = rest
+            .strip_prefix("**")
+            .filter(|r| bold || closed_on_line(r, "**"))
+        {
+            bold = !bold;
+            rest = r;
+        } else if let Some(r) = rest
+            .strip_prefix('*')
+            .filter(|r| italic || !r.starts_with('*') && closed_on_line(r, "*"))
+        {
+            italic = !italic;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix('\\') {
+            // markdown escapes, e.g. `\---` in headings written by aoc-cli.
+            let escaped = r.chars().next().unwrap_or('\\');
+            out.push((escaped, style(bold, italic)));
+            rest = &r[escaped.len_utf8().min(r.len())..];
+        } else if let Some(r) = rest.strip_prefix("](").filter(|_| link) {
+            // drop link targets, only the link text is shown.
+            rest = r.split_once(')').map_or("", |(_, r)| r);
+            link = false;
+        } else if let Some(r) = rest.strip_prefix('[').filter(|r| is_link(r)) {
+            rest = r;
+            link = true;
+        } else {
+            out.push((c, style(bold, italic)));
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    out
+}
+
+fn render_word(word: &[(char, Style)]) -> String {
+    let mut out = String::new();
+    let mut current = Style::Plain;
+
+    for &(c, style) in word {
+        if style != current {
+            if current != Style::Plain {
+                out.push_str(ANSI_RESET);
+            }
+            out.push_str(style.ansi());
+            current = style;
+        }
+        out.push(c);
+    }
+
+    if current != Style::Plain {
+        out.push_str(ANSI_RESET);
+    }
+    out
+}
+
+/// Wraps styled text to `width` columns. Continuation lines are indented by `indent` spaces.
+fn wrap(chars: &[(char, Style)], width: usize, indent: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut line_width = 0;
+
+    for word in chars
+        .split(|(c, _)| c.is_whitespace())
+        .filter(|w| !w.is_empty())
+    {
+        if line_width > 0 && line_width + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+            line.push_str(&" ".repeat(indent));
+            line_width = indent;
+        } else if line_width > 0 {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        line.push_str(&render_word(word));
+        line_width += word.len();
+    }
+
+    if line_width > 0 {
+        lines.push(line);
+    }
+    lines
+}
+
+fn render_code_block(code: &str) -> String {
+    let lines: Vec<&str> = code.lines().collect();
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    let mut out = vec![format!("┌{}┐", "─".repeat(width + 2))];
+    out.extend(
+        lines
+            .iter()
+            .map(|l| format!("│ {l}{} │", " ".repeat(width - l.chars().count()))),
+    );
+    out.push(format!("└{}┘", "─".repeat(width + 2)));
+    out.join("\n")
+}
+
+fn is_setext_underline(line: &str) -> bool {
+    line.len() >= 3 && line.chars().all(|c| c == '-' || c == '=')
+}
+
+fn render_text(text: &str, width: usize) -> String {
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+
+    let flush = |paragraph: &mut Vec<&str>, out: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            out.extend(wrap(
+                &parse_inline(&paragraph.join("\n"), Style::Plain),
+                width,
+                0,
+            ));
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines() {
+        if let Some(heading) = line.strip_prefix('#') {
+            flush(&mut paragraph, &mut out);
+            let heading = heading.trim_start_matches('#').trim();
+            out.extend(wrap(&parse_inline(heading, Style::Bold), width, 0));
+        } else if is_setext_underline(line) && !paragraph.is_empty() {
+            let heading = paragraph.join(" ");
+            paragraph.clear();
+            out.extend(wrap(&parse_inline(&heading, Style::Bold), width, 0));
+        } else if let Some(item) = line.strip_prefix("- ").or(line.strip_prefix("* ")) {
+            flush(&mut paragraph, &mut out);
+            let mut chars = vec![('-', Style::Plain), (' ', Style::Plain)];
+            chars.extend(parse_inline(item, Style::Plain));
+            out.extend(wrap(&chars, width, 2));
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+
+    flush(&mut paragraph, &mut out);
+    out.join("\n")
+}
+
+/// Renders a puzzle description with ANSI styles, wrapped to `width` columns.
+/// Code blocks are boxed and never wrapped, as their layout usually matters.
+pub fn render(markdown: &str, width: usize) -> String {
+    puzzle::blocks(markdown)
+        .iter()
+        .map(|block| match block {
+            Block::Text(text) => render_text(text, width),
+            Block::Code(code) => render_code_block(code),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_inline, render, render_code_block, wrap, Style, ANSI_HIGHLIGHT};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn parses_inline_styles() {
+        let chars = parse_inline("a *b* **c** `d` *`e`* [f](https://g) \\*", Style::Plain);
+        let styled: Vec<_> = chars.into_iter().filter(|(c, _)| *c != ' ').collect();
+        assert_eq!(
+            styled,
+            vec![
+                ('a', Style::Plain),
+                ('b', Style::Italic),
+                ('c', Style::Bold),
+                ('d', Style::Plain),
+                ('e', Style::Highlight),
+                ('f', Style::Plain),
+                ('*', Style::Plain),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_unmatched_markers() {
+        let text = |chars: Vec<(char, Style)>| -> (String, bool) {
+            let plain = chars.iter().all(|(_, style)| *style == Style::Plain);
+            (chars.into_iter().map(|(c, _)| c).collect(), plain)
+        };
+
+        assert_eq!(
+            text(parse_inline("2 * 3 = 6\nand *more*", Style::Plain)),
+            ("2 * 3 = 6\nand more".into(), false)
+        );
+        assert_eq!(
+            text(parse_inline("[A] [B] [C](x)", Style::Plain)),
+            ("[A] [B] C".into(), true)
+        );
+        assert_eq!(
+            text(parse_inline("a ** b\n[A](x", Style::Plain)),
+            ("a ** b\n[A](x".into(), true)
+        );
+    }
+
+    #[test]
+    fn wraps_words() {
+        let chars = parse_inline("aaa bbb ccc ddd", Style::Plain);
+        assert_eq!(wrap(&chars, 7, 0), vec!["aaa bbb", "ccc ddd"]);
+        assert_eq!(wrap(&chars, 8, 2), vec!["aaa bbb", "  ccc", "  ddd"]);
+    }
+
+    #[test]
+    fn wraps_by_visible_width() {
+        let chars = parse_inline("*`42`* is", Style::Plain);
+        assert_eq!(
+            wrap(&chars, 5, 0),
+            vec![format!("{ANSI_HIGHLIGHT}42{ANSI_RESET} is")]
+        );
+    }
+
+    #[test]
+    fn boxes_code_blocks() {
+        assert_eq!(
+            render_code_block("ab\nc\n"),
+            ["┌────┐", "│ ab │", "│ c  │", "└────┘"].join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_puzzle() {
+        let markdown = "## --- Day 1 ---\n\nThe *elves*\nsay:\n\n```\n1 2\n```\n\n- one\n- two\n\nResult: *`3`*.\n";
+        let expected = [
+            format!("{ANSI_BOLD}---{ANSI_RESET} {ANSI_BOLD}Day{ANSI_RESET} {ANSI_BOLD}1{ANSI_RESET} {ANSI_BOLD}---{ANSI_RESET}"),
+            String::new(),
+            format!("The {ANSI_ITALIC}elves{ANSI_RESET} say:"),
+            String::new(),
+            "┌─────┐\n│ 1 2 │\n└─────┘".into(),
+            String::new(),
+            "- one\n- two".into(),
+            String::new(),
+            format!("Result: {ANSI_HIGHLIGHT}3{ANSI_RESET}."),
+        ]
+        .join("\n");
+        assert_eq!(render(markdown, 80), expected);
+    }
+
+    #[test]
+    fn renders_setext_headings() {
+        assert_eq!(
+            render("\\--- Day 1 ---\n----------\n", 80),
+            format!("{ANSI_BOLD}---{ANSI_RESET} {ANSI_BOLD}Day{ANSI_RESET} {ANSI_BOLD}1{ANSI_RESET} {ANSI_BOLD}---{ANSI_RESET}")
+        );
+    }
+}
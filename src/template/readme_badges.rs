@@ -0,0 +1,288 @@
+/// Module that renders shields-style status badges and embeds them in the readme.
+/// Badges are plain SVG files, so the readme does not depend on external badge services.
+use std::{fs, io, path::Path};
+
+use crate::template::timings::Timings;
+
+static MARKER: &str = "<!--- status badges --->";
+static STARS_MARKER: &str = "<!--- advent_readme_stars table --->";
+static BADGES_DIR: &str = "./.assets";
+
+const CHAR_WIDTH: usize = 7;
+const PADDING: usize = 10;
+
+/// A single badge, e.g. `stars | 30/50`.
+struct Badge {
+    file_name: &'static str,
+    label: &'static str,
+    value: String,
+    color: &'static str,
+}
+
+impl Badge {
+    fn path(&self) -> String {
+        format!("{BADGES_DIR}/{}", self.file_name)
+    }
+
+    fn to_svg(&self) -> String {
+        let label_width = self.label.chars().count() * CHAR_WIDTH + PADDING;
+        let value_width = self.value.chars().count() * CHAR_WIDTH + PADDING;
+        let width = label_width + value_width;
+        let (label, value, color) = (self.label, &self.value, self.color);
+
+        [
+            format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" role=\"img\" aria-label=\"{label}: {value}\">"),
+            format!("<title>{label}: {value}</title>"),
+            "<linearGradient id=\"s\" x2=\"0\" y2=\"100%\"><stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/><stop offset=\"1\" stop-opacity=\".1\"/></linearGradient>".into(),
+            format!("<clipPath id=\"r\"><rect width=\"{width}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>"),
+            format!("<g clip-path=\"url(#r)\"><rect width=\"{label_width}\" height=\"20\" fill=\"#555\"/><rect x=\"{label_width}\" width=\"{value_width}\" height=\"20\" fill=\"{color}\"/><rect width=\"{width}\" height=\"20\" fill=\"url(#s)\"/></g>"),
+            "<g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">".into(),
+            format!("<text x=\"{}\" y=\"14\">{label}</text>", label_width / 2),
+            format!("<text x=\"{}\" y=\"14\">{value}</text>", label_width + value_width / 2),
+            "</g>".into(),
+            "</svg>".into(),
+            String::new(),
+        ]
+        .join("\n")
+    }
+
+    fn to_markdown(&self) -> String {
+        format!("![{}: {}]({})", self.label, self.value, self.path())
+    }
+}
+
+/// Stars collected and days with both stars.
+#[derive(Debug, PartialEq, Eq)]
+struct Progress {
+    stars: usize,
+    days_solved: usize,
+}
+
+impl Progress {
+    /// Reads progress from the table maintained by the `advent-readme-stars` action if present,
+    /// otherwise treats every benchmarked part as a collected star.
+    fn new(readme: &str, timings: &Timings) -> Self {
+        Self::from_stars_table(readme).unwrap_or_else(|| Self::from_timings(timings))
+    }
+
+    fn from_stars_table(readme: &str) -> Option<Self> {
+        let (_, section) = readme.split_once(STARS_MARKER)?;
+        // the table ends at the next marker comment, e.g. the benchmarking table.
+        let section = section.split("<!---").next()?;
+
+        let rows: Vec<usize> = section
+            .lines()
+            .skip_while(|line| !line.starts_with('|'))
+            .take_while(|line| line.starts_with('|'))
+            .filter(|line| line.starts_with("| [Day"))
+            .map(|line| line.matches('⭐').count())
+            .collect();
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            stars: rows.iter().sum(),
+            days_solved: rows.iter().filter(|stars| **stars >= 2).count(),
+        })
+    }
+
+    fn from_timings(timings: &Timings) -> Self {
+        Self {
+            stars: timings
+                .data
+                .iter()
+                .map(|t| usize::from(t.part_1.is_some()) + usize::from(t.part_2.is_some()))
+                .sum(),
+            days_solved: timings
+                .data
+                .iter()
+                .filter(|t| timings.is_day_complete(t.day))
+                .count(),
+        }
+    }
+}
+
+fn runtime_color(total_millis: f64) -> &'static str {
+    match total_millis {
+        x if x < 100.0 => "#2da44e",
+        x if x < 1000.0 => "#d4a72c",
+        _ => "#cf222e",
+    }
+}
+
+fn construct_badges(readme: &str, timings: &Timings) -> Vec<Badge> {
+    let progress = Progress::new(readme, timings);
+    let total_millis = timings.total_millis();
+
+    vec![
+        Badge {
+            file_name: "badge-stars.svg",
+            label: "stars",
+            value: format!("{}/50", progress.stars),
+            color: "#d4a72c",
+        },
+        Badge {
+            file_name: "badge-days.svg",
+            label: "days solved",
+            value: format!("{}/25", progress.days_solved),
+            color: "#2da44e",
+        },
+        Badge {
+            file_name: "badge-runtime.svg",
+            label: "total runtime",
+            value: format!("{total_millis:.2}ms"),
+            color: runtime_color(total_millis),
+        },
+    ]
+}
+
+/// Replaces the content between the badge markers.
+/// Readmes without exactly one pair of markers are left untouched.
+fn update_content(s: &mut String, badges: &[Badge]) {
+    let matches: Vec<_> = s.match_indices(MARKER).map(|m| m.0).collect();
+
+    let [pos_start, pos_end] = matches[..] else {
+        return;
+    };
+
+    let mut lines = vec![MARKER.to_string()];
+    lines.extend(badges.iter().map(Badge::to_markdown));
+    lines.push(MARKER.into());
+
+    s.replace_range(pos_start..pos_end + MARKER.len(), &lines.join("\n"));
+}
+
+/// Writes badge SVG files for `timings` and embeds them in `readme`.
+pub fn update(readme: &mut String, timings: &Timings) -> Result<(), io::Error> {
+    let badges = construct_badges(readme, timings);
+
+    fs::create_dir_all(Path::new(BADGES_DIR))?;
+    for badge in &badges {
+        fs::write(badge.path(), badge.to_svg())?;
+    }
+
+    update_content(readme, &badges);
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_badges, update_content, Progress, MARKER, STARS_MARKER};
+    use crate::{day, template::timings::Timing, template::timings::Timings};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20ms".into()),
+                    total_nanos: 3e+7,
+                    build: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("30ms".into()),
+                    part_2: None,
+                    total_nanos: 3e+7,
+                    build: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn counts_progress_from_timings() {
+        let progress = Progress::new("# readme", &get_mock_timings());
+        assert_eq!(
+            progress,
+            Progress {
+                stars: 3,
+                days_solved: 1
+            }
+        );
+    }
+
+    #[test]
+    fn counts_progress_from_stars_table() {
+        let readme = [
+            STARS_MARKER,
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ | ⭐ |",
+            "| [Day 3](https://adventofcode.com/2023/day/3) | ⭐ |   |",
+            "",
+            "<!--- benchmarking table --->",
+            "| [Day 4](./src/bin/04.rs) | ⭐ | ⭐ |",
+        ]
+        .join("\n");
+
+        let progress = Progress::new(&readme, &get_mock_timings());
+        assert_eq!(
+            progress,
+            Progress {
+                stars: 5,
+                days_solved: 2
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_empty_stars_table() {
+        let readme = format!("{STARS_MARKER}\n\n<!--- benchmarking table --->\n| [Day 1](./src/bin/01.rs) | `1ms` | `2ms` |");
+        let progress = Progress::new(&readme, &get_mock_timings());
+        assert_eq!(progress.stars, 3);
+    }
+
+    #[test]
+    fn renders_badges() {
+        let badges = construct_badges("", &get_mock_timings());
+        assert_eq!(badges.len(), 3);
+        assert_eq!(badges[0].value, "3/50");
+        assert_eq!(badges[1].value, "1/25");
+        assert_eq!(badges[2].value, "60.00ms");
+        assert!(badges[2]
+            .to_svg()
+            .contains("<title>total runtime: 60.00ms</title>"));
+    }
+
+    #[test]
+    fn updates_badges_between_markers() {
+        let mut s = format!("# title\n{MARKER}{MARKER}\nfoo");
+        let badges = construct_badges("", &get_mock_timings());
+        update_content(&mut s, &badges);
+        update_content(&mut s, &badges);
+
+        let expected = [
+            "# title",
+            MARKER,
+            "![stars: 3/50](./.assets/badge-stars.svg)",
+            "![days solved: 1/25](./.assets/badge-days.svg)",
+            "![total runtime: 60.00ms](./.assets/badge-runtime.svg)",
+            MARKER,
+            "foo",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn ignores_readme_without_markers() {
+        let mut s = "# title".to_string();
+        update_content(&mut s, &construct_badges("", &get_mock_timings()));
+        assert_eq!(s, "# title");
+    }
+
+    #[test]
+    fn ignores_readme_with_too_many_markers() {
+        let mut s = format!("{MARKER} {MARKER} {MARKER}");
+        let expected = s.clone();
+        update_content(&mut s, &construct_badges("", &get_mock_timings()));
+        assert_eq!(s, expected);
+    }
+}
@@ -2,8 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::benchmark_chart::CHART_FILE_PATH;
+use crate::template::bin_path::get_path_for_bin;
+use crate::template::readme_badges;
 use crate::template::timings::Timings;
-use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -13,6 +15,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => write!(f, "{s}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -24,11 +35,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -51,6 +57,34 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+/// Describes the build options of `timings`, so benchmarks of different builds are not compared by accident.
+fn build_note(timings: &Timings) -> Option<String> {
+    let mut builds: Vec<Option<&str>> = timings.data.iter().map(|t| t.build.as_deref()).collect();
+    builds.dedup();
+
+    match builds.as_slice() {
+        [] | [None] => None,
+        [Some(build)] => Some(format!("_Built with: {build}._")),
+        _ => {
+            let days: Vec<String> = timings
+                .data
+                .iter()
+                .map(|t| {
+                    format!(
+                        "{} ({})",
+                        t.day.into_inner(),
+                        t.build.as_deref().unwrap_or("default")
+                    )
+                })
+                .collect();
+            Some(format!(
+                "_Mixed build options, run `cargo time --all` to re-time with the same options: {}._",
+                days.join(", ")
+            ))
+        }
+    }
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
@@ -58,10 +92,14 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
         MARKER.into(),
         header,
         String::new(),
+        format!("![Benchmark chart]({CHART_FILE_PATH})"),
+        String::new(),
         "| Day | Part 1 | Part 2 |".into(),
         "| :---: | :---: | :---:  |".into(),
     ];
 
+    let note = build_note(&timings);
+
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
@@ -75,6 +113,10 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+    if let Some(note) = note {
+        lines.push(String::new());
+        lines.push(note);
+    }
     lines.push(MARKER.into());
 
     lines.join("\n")
@@ -91,6 +133,7 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
+    readme_badges::update(&mut readme, &timings)?;
     update_content(&mut readme, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
@@ -98,7 +141,7 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
+    use super::{build_note, update_content, MARKER};
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
@@ -109,18 +152,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    build: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    build: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    build: None,
                 },
             ],
         }
@@ -166,6 +212,8 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
+            "![Benchmark chart](./.assets/benchmarks.svg)",
+            "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
@@ -179,4 +227,20 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn notes_build_options() {
+        let mut timings = get_mock_timings();
+        for timing in &mut timings.data {
+            timing.build = Some("native".into());
+        }
+        assert_eq!(build_note(&timings), Some("_Built with: native._".into()));
+
+        timings.data[1].build = None;
+        let note = build_note(&timings).unwrap();
+        assert!(note.starts_with("_Mixed build options"));
+        assert!(note.ends_with("1 (native), 2 (default), 4 (native)._"));
+
+        assert_eq!(build_note(&get_mock_timings()), None);
+    }
 }
@@ -1,13 +1,18 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{build_options::BuildOptions, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    options: &BuildOptions,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,12 +29,19 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = match child_commands::run_solution(day, is_timed, is_release, options) {
+                Ok(output) => output,
+                Err(e) => {
+                    eprintln!("Failed to run solution: {e}");
+                    return;
+                }
+            };
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let mut val = child_commands::parse_exec_time(&output, day);
+                val.build = options.label();
                 timings.push(val);
             }
         });
@@ -40,6 +52,9 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
+        if let Some(label) = options.label() {
+            println!("Built with: {label}");
+        }
         Some(timings)
     } else {
         None
@@ -52,53 +67,68 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture output of child process."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        bin_path::find_path_for_bin, build_options::BuildOptions, timings::parse_duration, Day,
+    };
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        options: &BuildOptions,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if find_path_for_bin(day).is_none() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".to_string(),
+            "--bin".to_string(),
+            day.to_string(),
+        ];
 
-        if is_release {
-            args.push("--release");
-        }
+        args.extend(options.cargo_args(is_release));
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--".to_string());
+            args.push("--time".to_string());
         }
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new("cargo");
+        options.apply_env(&mut cmd);
+
+        let mut cmd = cmd
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -133,6 +163,7 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            build: None,
         };
 
         output
@@ -163,27 +194,17 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
-            .split('(')
-            .last()?
+            .rsplit('(')
+            .next()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_duration(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -2,21 +2,28 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::dhat_report::{self, HeapSummary};
+use crate::template::submissions::{self, Outcome, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{answers, aoc_cli, aoc_client, Day, ANSI_ITALIC, ANSI_RESET};
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let report_path = dhat_report::get_report_path(day, part);
+    let (result, duration, samples) = run_timed(func, input, &report_path, |result| {
+        print_result(result, &part_str, "");
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
+    if cfg!(feature = "dhat-heap") {
+        print_heap_summary(day, part);
+    }
+
     if let Some(result) = result {
         submit_result(result, day, part);
     }
@@ -25,9 +32,13 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With the `dhat-heap` feature, the first execution is profiled and its report is written to `report_path`.
+#[cfg_attr(not(feature = "dhat-heap"), allow(unused_variables))]
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    report_path: &str,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
@@ -35,7 +46,7 @@ fn run_timed<I: Clone, T>(
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = dhat::Profiler::builder().file_name(report_path).build();
 
         func(input)
     };
@@ -58,13 +69,8 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -91,6 +97,18 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
+/// Prints the key totals of the DHAT report of a part.
+fn print_heap_summary(day: Day, part: u8) {
+    match HeapSummary::read(day, part) {
+        Some(Ok(summary)) => println!("Part {part} heap: {}", summary.to_compact_string()),
+        Some(Err(e)) => eprintln!("Failed to read DHAT report: {e}"),
+        None => eprintln!(
+            "DHAT report \"{}\" was not written.",
+            dhat_report::get_report_path(day, part)
+        ),
+    }
+}
+
 fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
@@ -136,16 +154,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is configured (or aoc-cli is installed when `AOC_CLIENT=aoc-cli`).
+///  3. the part is not solved yet and the answer is not a duplicate or provably wrong according to `data/submissions.json`.
+///
+/// Accepted answers are stored in `data/answers/{day}.txt`.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -161,14 +178,82 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let answer = result.to_string();
+
+    if let Some(solved) = answers::read_answer(day, part) {
+        eprintln!(
+            "Refusing to submit `{answer}`: this part is already solved with answer `{solved}`."
+        );
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let mut submissions = Submissions::read_from_file();
+
+    if let Err(rejection) = submissions.check(day, part, &answer, submissions::now()) {
+        eprintln!("Refusing to submit `{answer}`: {rejection}");
+        process::exit(1);
+    }
+
+    let message = if aoc_client::use_aoc_cli() {
+        if aoc_cli::check().is_err() {
+            eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+            process::exit(1);
+        }
+
+        println!("Submitting result via aoc-cli...");
+        match aoc_cli::submit(day, part, &answer) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        println!("Submitting result...");
+        match aoc_client::submit(day, part, &answer) {
+            Ok(message) => {
+                println!("{message}");
+                message
+            }
+            Err(e) => {
+                eprintln!("failed to submit result: {e}");
+                process::exit(1);
+            }
+        }
+    };
+
+    let outcome = submissions.record(day, part, &answer, &message, submissions::now());
+    if let Err(e) = submissions.store_file() {
+        eprintln!("failed to store submission history: {e}");
+    }
+
+    if outcome == Outcome::Correct {
+        record_correct_answer(day, part, &answer);
+    }
+}
+
+/// Stores an accepted answer and re-downloads the puzzle, so that the next part's description is available.
+fn record_correct_answer(day: Day, part: u8, answer: &str) {
+    match answers::store_answer(day, part, answer) {
+        Ok(()) => println!(
+            "🎄 Marked part {part} as solved in \"{}\".",
+            answers::get_answers_path(day)
+        ),
+        Err(e) => eprintln!("failed to store answer: {e}"),
+    }
+
+    let downloaded = if aoc_client::use_aoc_cli() {
+        aoc_cli::download(day, false, true)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    } else {
+        aoc_client::download(day, false, true).map_err(|e| e.to_string())
+    };
+
+    if let Err(e) = downloaded {
+        eprintln!("failed to re-download puzzle: {e}");
+    }
 }
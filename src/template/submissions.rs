@@ -0,0 +1,499 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{self, Error},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// The category of a response to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    Wait,
+    Unknown,
+}
+
+impl Outcome {
+    /// Categorizes the message returned by the website.
+    pub fn from_message(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            Outcome::Correct
+        } else if message.contains("too recently") {
+            Outcome::Wait
+        } else if message.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if message.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if message.contains("That's not the right answer") {
+            Outcome::Incorrect
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::Wait => "wait",
+            Outcome::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Outcome::Correct,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+            Outcome::Incorrect,
+            Outcome::Wait,
+            Outcome::Unknown,
+        ]
+        .into_iter()
+        .find(|x| x.as_str() == s)
+        .ok_or_else(|| format!("Unknown submission outcome `{s}`."))
+    }
+}
+
+/// Parses the cooldown (in seconds) the website asks for, e.g. "You have 1m 12s left to wait"
+/// or "please wait one minute before trying again".
+pub fn parse_cooldown(message: &str) -> Option<u64> {
+    if let Some((_, rest)) = message.split_once("You have ") {
+        let (duration, _) = rest.split_once(" left to wait")?;
+        return duration
+            .split_whitespace()
+            .map(|token| {
+                let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum();
+    }
+
+    let (_, rest) = message.split_once("wait ")?;
+    let (amount, rest) = rest.split_once(' ')?;
+    if !rest.starts_with("minute") {
+        return None;
+    }
+    let minutes = match amount {
+        "one" => 1,
+        x => x.parse().ok()?,
+    };
+    Some(minutes * 60)
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    pub timestamp: u64,
+    pub cooldown_until: Option<u64>,
+}
+
+/// The reason a submission was refused locally.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadySolved(String),
+    Duplicate(Outcome),
+    AboveTooHigh(String),
+    BelowTooLow(String),
+    Cooldown(u64),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadySolved(answer) => {
+                write!(f, "this part is already solved with answer `{answer}`.")
+            }
+            Rejection::Duplicate(outcome) => {
+                write!(f, "this answer was already submitted ({outcome}).")
+            }
+            Rejection::AboveTooHigh(answer) => {
+                write!(f, "`{answer}` was already too high.")
+            }
+            Rejection::BelowTooLow(answer) => {
+                write!(f, "`{answer}` was already too low.")
+            }
+            Rejection::Cooldown(secs) => {
+                write!(f, "please wait another {}m {}s.", secs / 60, secs % 60)
+            }
+        }
+    }
+}
+
+/// Local history of submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file() -> Self {
+        let s = match fs::read_to_string(SUBMISSIONS_FILE_PATH) {
+            Ok(s) => Submissions::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Submissions::default(),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match s {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                eprintln!("{e}");
+                Submissions::default()
+            }
+        }
+    }
+
+    fn for_part(&self, day: Day, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// Checks whether `answer` is worth submitting at time `now`.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Rejection> {
+        if let Some(correct) = self
+            .for_part(day, part)
+            .find(|s| s.outcome == Outcome::Correct)
+        {
+            return Err(Rejection::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(duplicate) = self
+            .for_part(day, part)
+            .find(|s| s.answer == answer && !matches!(s.outcome, Outcome::Wait | Outcome::Unknown))
+        {
+            return Err(Rejection::Duplicate(duplicate.outcome));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            for submission in self.for_part(day, part) {
+                let Ok(known) = submission.answer.parse::<i128>() else {
+                    continue;
+                };
+                if submission.outcome == Outcome::TooHigh && value >= known {
+                    return Err(Rejection::AboveTooHigh(submission.answer.clone()));
+                }
+                if submission.outcome == Outcome::TooLow && value <= known {
+                    return Err(Rejection::BelowTooLow(submission.answer.clone()));
+                }
+            }
+        }
+
+        let cooldown_until = self
+            .data
+            .iter()
+            .filter(|s| s.day == day)
+            .filter_map(|s| s.cooldown_until)
+            .max();
+
+        match cooldown_until {
+            Some(until) if until > now => Err(Rejection::Cooldown(until - now)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the website's response to a submitted answer.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, message: &str, now: u64) -> Outcome {
+        let outcome = Outcome::from_message(message);
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp: now,
+            cooldown_until: parse_cooldown(message).map(|secs| now + secs),
+        });
+        outcome
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.to_string()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "cooldown_until".into(),
+            match value.cooldown_until {
+                Some(x) => JsonValue::Number(x as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.outcome to be a string.")?
+            .parse()?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let cooldown_until = json
+            .get("cooldown_until")
+            .map(|v| v.get::<f64>().map(|x| *x as u64))
+            .ok_or("Expected submission.cooldown_until to be null or number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+            timestamp,
+            cooldown_until,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cooldown, Outcome, Rejection, Submissions};
+    use crate::day;
+
+    const TOO_HIGH: &str = "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.";
+    const TOO_LOW: &str = "That's not the right answer; your answer is too low.  Please wait one minute before trying again.";
+    const TOO_RECENT: &str = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait.";
+    const CORRECT: &str =
+        "That's the right answer!  You are one gold star closer to restoring snow operations.";
+
+    #[test]
+    fn categorizes_messages() {
+        assert_eq!(Outcome::from_message(TOO_HIGH), Outcome::TooHigh);
+        assert_eq!(Outcome::from_message(TOO_LOW), Outcome::TooLow);
+        assert_eq!(Outcome::from_message(TOO_RECENT), Outcome::Wait);
+        assert_eq!(Outcome::from_message(CORRECT), Outcome::Correct);
+        assert_eq!(
+            Outcome::from_message(
+                "That's not the right answer.  Please wait 5 minutes before trying again."
+            ),
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            Outcome::from_message("You don't seem to be solving the right level."),
+            Outcome::Unknown
+        );
+    }
+
+    #[test]
+    fn parses_cooldowns() {
+        assert_eq!(parse_cooldown(TOO_HIGH), Some(60));
+        assert_eq!(parse_cooldown(TOO_RECENT), Some(72));
+        assert_eq!(parse_cooldown("You have 34s left to wait."), Some(34));
+        assert_eq!(
+            parse_cooldown("Please wait 5 minutes before trying again."),
+            Some(300)
+        );
+        assert_eq!(parse_cooldown(CORRECT), None);
+    }
+
+    #[test]
+    fn refuses_provably_wrong_answers() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100", TOO_HIGH, 0);
+        submissions.record(day!(1), 1, "10", TOO_LOW, 0);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "150", 1000),
+            Err(Rejection::AboveTooHigh("100".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "5", 1000),
+            Err(Rejection::BelowTooLow("10".into()))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "50", 1000), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "150", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_duplicates() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "abc", "That's not the right answer.", 0);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "abc", 1000),
+            Err(Rejection::Duplicate(Outcome::Incorrect))
+        );
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "42", CORRECT, 0);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "43", 1000),
+            Err(Rejection::AlreadySolved("42".into()))
+        );
+    }
+
+    #[test]
+    fn reports_remaining_cooldown() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "42", TOO_RECENT, 100);
+
+        assert_eq!(
+            submissions.check(day!(1), 1, "42", 130),
+            Err(Rejection::Cooldown(42))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "42", 172), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(3), 2, "42", TOO_HIGH, 1_701_388_800);
+        submissions.record(day!(3), 2, "41", CORRECT, 1_701_388_900);
+
+        let json = tinyjson::JsonValue::from(submissions).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+
+        assert_eq!(parsed.data.len(), 2);
+        assert_eq!(parsed.data[0].day, day!(3));
+        assert_eq!(parsed.data[0].part, 2);
+        assert_eq!(parsed.data[0].outcome, Outcome::TooHigh);
+        assert_eq!(parsed.data[0].cooldown_until, Some(1_701_388_860));
+        assert_eq!(parsed.data[1].outcome, Outcome::Correct);
+        assert_eq!(parsed.data[1].cooldown_until, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_submissions() {
+        let json = r#"{ "data": [{ "day": "01", "part": 1 }] }"#.to_string();
+        Submissions::try_from(json).unwrap();
+    }
+}
@@ -12,6 +12,8 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Label of the build options the timing was taken with, `None` for a default build.
+    pub build: Option<String>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -58,7 +60,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -72,6 +74,32 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Whether the stored timing of `day` was taken with the build options labelled `build`.
+    /// Days without a timing count as matching.
+    pub fn is_day_built_with(&self, day: Day, build: Option<&str>) -> bool {
+        self.data
+            .iter()
+            .filter(|t| t.day == day)
+            .all(|t| t.build.as_deref() == build)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parse a formatted duration (e.g. `74.13µs`) into nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -140,6 +168,10 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(build) = &value.build {
+            map.insert("build".into(), JsonValue::String(build.clone()));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,11 +205,23 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timings stored before build options were recorded have no `build` key.
+        let build = match json.get("build") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.build to be null or string.")?,
+            ),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            build,
         })
     }
 }
@@ -198,18 +242,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    build: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    build: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    build: None,
                 },
             ],
         }
@@ -230,6 +277,16 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_build_options() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "build": "native" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].build, Some("native".to_string()));
+            assert!(timings.is_day_built_with(day!(1), Some("native")));
+            assert!(!timings.is_day_built_with(day!(1), None));
+            assert!(timings.is_day_built_with(day!(2), None));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -289,10 +346,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    build: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +361,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    build: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,10 +376,11 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    build: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -341,6 +401,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    build: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +422,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    build: None,
                 }],
             };
             let merged = timings.merge(&other);
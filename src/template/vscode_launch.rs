@@ -0,0 +1,205 @@
+/// Module that adds debug configurations for a day to `.vscode/launch.json`.
+/// The file may contain comments and trailing commas, so it is edited as text instead of being parsed as JSON.
+use std::{fs, io, path::Path};
+
+use crate::template::Day;
+
+static LAUNCH_FILE_PATH: &str = ".vscode/launch.json";
+
+fn configuration(name: &str, cargo_args: &[&str], day: Day) -> String {
+    let args = cargo_args
+        .iter()
+        .map(|arg| format!("\"{arg}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    [
+        "        {".to_string(),
+        "            \"type\": \"lldb\",".into(),
+        "            \"request\": \"launch\",".into(),
+        format!("            \"name\": \"{name}\","),
+        "            \"cargo\": {".into(),
+        format!(
+            "                \"args\": [{args}, \"--bin={day}\", \"--package=advent_of_code\"],"
+        ),
+        format!("                \"filter\": {{ \"name\": \"{day}\", \"kind\": \"bin\" }}"),
+        "            },".into(),
+        "            \"args\": [],".into(),
+        "            \"cwd\": \"${workspaceFolder}\"".into(),
+        "        }".into(),
+    ]
+    .join("\n")
+}
+
+fn configurations(day: Day) -> String {
+    [
+        configuration(&format!("Debug Day {day}"), &["build"], day),
+        configuration(
+            &format!("Debug Day {day} tests"),
+            &["test", "--no-run"],
+            day,
+        ),
+    ]
+    .join(",\n")
+}
+
+/// Returns the index of the `]` that closes the `[` at `open`.
+/// Brackets inside strings and comments are skipped.
+fn closing_bracket(content: &str, open: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0;
+    let mut i = open;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    // skip the escaped character.
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i += content[i..].find('\n').unwrap_or(content.len() - i);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += content[i..].find("*/").map_or(content.len() - i, |x| x + 1);
+            }
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Appends the configurations for `day` to the `configurations` array of a launch file.
+/// Returns `None` if they are present already or the file has no `configurations` array.
+fn add_configurations(content: &str, day: Day) -> Option<String> {
+    if content.contains(&format!("\"name\": \"Debug Day {day}\"")) {
+        return None;
+    }
+
+    let key = content.find("\"configurations\"")?;
+    let start = key + content[key..].find('[')?;
+    let end = closing_bracket(content, start)?;
+
+    let before = content[..end].trim_end();
+    let separator = if before.ends_with('[') || before.ends_with(',') {
+        ""
+    } else {
+        ","
+    };
+
+    Some(format!(
+        "{before}{separator}\n{}\n    {}",
+        configurations(day),
+        &content[end..]
+    ))
+}
+
+fn empty_launch_file() -> String {
+    "{\n    \"version\": \"0.2.0\",\n    \"configurations\": [\n    ]\n}\n".into()
+}
+
+/// Adds "Debug Day NN" and "Debug Day NN tests" to `.vscode/launch.json`, creating the file if needed.
+/// Returns `false` if the configurations already exist.
+pub fn add_day(day: Day) -> Result<bool, io::Error> {
+    let content = fs::read_to_string(LAUNCH_FILE_PATH).unwrap_or_else(|_| empty_launch_file());
+
+    let Some(updated) = add_configurations(&content, day) else {
+        return Ok(false);
+    };
+
+    if let Some(dir) = Path::new(LAUNCH_FILE_PATH).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(LAUNCH_FILE_PATH, updated)?;
+    Ok(true)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{add_configurations, empty_launch_file};
+    use crate::day;
+
+    const LAUNCH_FILE: &str = r#"{
+    // a comment
+    "version": "0.2.0",
+    "configurations": [
+        {
+            "type": "lldb",
+            "name": "Debug a solution",
+            "cargo": { "args": ["build", "--bin=01"], },
+        }
+    ]
+}
+"#;
+
+    #[test]
+    fn appends_configurations() {
+        let updated = add_configurations(LAUNCH_FILE, day!(5)).unwrap();
+
+        assert!(updated.starts_with("{\n    // a comment"));
+        assert!(updated.contains("        },\n        {\n            \"type\": \"lldb\",\n            \"request\": \"launch\",\n            \"name\": \"Debug Day 05\","));
+        assert!(
+            updated.contains("\"args\": [\"build\", \"--bin=05\", \"--package=advent_of_code\"],")
+        );
+        assert!(updated.contains("\"name\": \"Debug Day 05 tests\","));
+        assert!(updated.contains(
+            "\"args\": [\"test\", \"--no-run\", \"--bin=05\", \"--package=advent_of_code\"],"
+        ));
+        assert!(updated.ends_with("        }\n    ]\n}\n"));
+    }
+
+    #[test]
+    fn is_idempotent() {
+        let updated = add_configurations(LAUNCH_FILE, day!(5)).unwrap();
+        assert_eq!(add_configurations(&updated, day!(5)), None);
+        assert!(add_configurations(&updated, day!(6)).is_some());
+    }
+
+    #[test]
+    fn fills_empty_launch_file() {
+        let updated = add_configurations(&empty_launch_file(), day!(1)).unwrap();
+        assert!(updated.contains("\"configurations\": [\n        {"));
+        assert!(updated.ends_with("        }\n    ]\n}\n"));
+    }
+
+    #[test]
+    fn inserts_into_configurations_before_later_arrays() {
+        let content = r#"{
+    "configurations": [
+        { "name": "a ] [ \"]", /* ] */ "args": [] } // ]
+    ],
+    "compounds": [
+        { "name": "both", "configurations": ["a"] }
+    ]
+}
+"#;
+        let updated = add_configurations(content, day!(5)).unwrap();
+        let (configurations, compounds) = updated.split_once("\"compounds\"").unwrap();
+
+        assert!(configurations.contains("\"name\": \"Debug Day 05 tests\","));
+        assert!(configurations.trim_end().ends_with("}\n    ],"));
+        assert_eq!(
+            compounds,
+            ": [\n        { \"name\": \"both\", \"configurations\": [\"a\"] }\n    ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn ignores_files_without_configurations() {
+        assert_eq!(add_configurations("{}", day!(1)), None);
+    }
+}